@@ -1,25 +1,119 @@
+use std::path::{Path, PathBuf};
+
 use eframe::egui;
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Serialize, Deserialize)]
+const MAX_RECENT_WORKSPACES: usize = 8;
+/// Seconds without input before changes are saved.
+const AUTOSAVE_DELAY: f64 = 1.0;
+
+#[derive(Default, PartialEq, Serialize, Deserialize)]
 enum EditTab {
+    #[default]
     Questions,
     Preview,
     Responses,
     Settings,
 }
 
-impl Default for EditTab {
-    fn default() -> Self {
-        Self::Questions
+/// Order of the forms list on the main menu.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum FormSort {
+    #[default]
+    Modified,
+    Created,
+    Name,
+    Responses,
+}
+
+impl FormSort {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Modified => "Last modified",
+            Self::Created => "Date created",
+            Self::Name => "Name",
+            Self::Responses => "Responses",
+        }
     }
 }
 
+/// A change to the forms list waiting for the user to confirm it.
+#[derive(Clone, Copy)]
+enum Confirm {
+    Trash(usize),
+    EmptyTrash,
+}
+
+/// Forms kept in app storage because their folder couldn't be written, until
+/// the folder is next opened.
+#[derive(Serialize, Deserialize)]
+struct UnsavedForms {
+    dir: PathBuf,
+    /// The forms with changes not yet in the folder, each with the name of its
+    /// files there, if it has any yet.
+    forms: Vec<(Option<String>, crate::form::Form)>,
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct EformApp {
     forms: Vec<crate::form::Form>,
+    /// The form being edited. Not kept, as forms load from their folders in
+    /// file name order.
+    #[serde(skip)]
     form_index: Option<usize>,
     edit_tab: EditTab,
+    #[serde(skip)]
+    report_path: String,
+    #[serde(skip)]
+    report_status: String,
+    #[serde(skip)]
+    csv_path: String,
+    /// Folder to save uploaded files to.
+    #[serde(skip)]
+    attachments_path: String,
+    /// The folder the user opened, or `None` for eform's own forms.
+    #[serde(default)]
+    workspace: Option<PathBuf>,
+    #[serde(default)]
+    recent_workspaces: Vec<PathBuf>,
+    /// eform's own forms, set aside while a folder is open.
+    #[serde(skip)]
+    local_forms: Vec<crate::form::Form>,
+    #[serde(default)]
+    unsaved: Vec<UnsavedForms>,
+    #[serde(skip)]
+    workspace_path: String,
+    #[serde(skip)]
+    workspace_status: String,
+    /// How many forms there were when they were last autosaved to app storage,
+    /// to notice deleted ones.
+    #[serde(skip)]
+    stored_forms: usize,
+    /// When input first came in since the last autosave.
+    #[serde(skip)]
+    input_time: Option<f64>,
+    /// Backups listed on the restore screen, which is shown while this is `Some`.
+    #[serde(skip)]
+    backups: Option<Vec<crate::workspace::Backup>>,
+    #[serde(skip)]
+    confirm: Option<Confirm>,
+    /// Forms the user saved as templates, shown after the built-in ones.
+    #[serde(default)]
+    templates: Vec<crate::form::Form>,
+    #[serde(skip)]
+    search: String,
+    #[serde(default)]
+    sort: FormSort,
+    /// Only forms with this tag are listed, if set.
+    #[serde(default)]
+    tag_filter: Option<String>,
+    #[serde(default)]
+    show_archived: bool,
+    #[serde(skip)]
+    new_tag: String,
+    /// How dates and times are written in new forms.
+    #[serde(default)]
+    locale: crate::Locale,
 }
 
 impl EformApp {
@@ -28,61 +122,569 @@ impl EformApp {
             return Self::default();
         };
 
-        match eframe::get_value(storage, "data") {
-            None => Self::default(),
-            Some(data) => data,
+        let mut app: Self = eframe::get_value(storage, "data").unwrap_or_default();
+        if let Some(dir) = crate::workspace::default_dir() {
+            match crate::workspace::load(&dir) {
+                // Forms kept in app storage by earlier versions move into the
+                // folder on the next save.
+                Ok((mut forms, skipped)) => {
+                    forms.append(&mut app.forms);
+                    app.forms = forms;
+                    app.workspace_status = skipped.join("\n");
+                }
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                Err(error) => {
+                    app.workspace_status = format!("Could not open {}: {}", dir.display(), error)
+                }
+            }
+            let forms = std::mem::take(&mut app.forms);
+            app.forms = app.restore_unsaved(&dir, forms);
+        }
+        if let Some(path) = app.workspace.take() {
+            app.open_workspace(path);
         }
+        app
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn run() {
         eframe::run_native(
             "eform",
             eframe::NativeOptions::default(),
             Box::new(|cc| Box::new(Self::new(cc))),
-        ).unwrap();
+        )
+        .unwrap();
     }
 
-    fn main_menu(&mut self, ctx: &egui::Context) {
+    /// Starts the app on the page's `<canvas id="eform">`, keeping forms in the
+    /// browser's local storage.
+    #[cfg(target_arch = "wasm32")]
+    pub fn run() {
+        wasm_bindgen_futures::spawn_local(async {
+            eframe::start_web(
+                "eform",
+                eframe::WebOptions::default(),
+                Box::new(|cc| Box::new(Self::new(cc))),
+            )
+            .await
+            .expect("failed to start eform");
+        });
+    }
+
+    /// Switches to the forms in the folder at `path`, saving the forms open
+    /// before it.
+    fn open_workspace(&mut self, path: PathBuf) {
+        let (forms, skipped) = match crate::workspace::load(&path) {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                self.workspace_status = format!("Could not open {}: {}", path.display(), error);
+                return;
+            }
+        };
+        if !self.close_workspace() || !self.save_forms() {
+            return;
+        }
+        let forms = self.restore_unsaved(&path, forms);
+        self.local_forms = std::mem::replace(&mut self.forms, forms);
+        self.form_index = None;
+        self.recent_workspaces.retain(|recent| *recent != path);
+        self.recent_workspaces.insert(0, path.clone());
+        self.recent_workspaces.truncate(MAX_RECENT_WORKSPACES);
+        self.workspace = Some(path);
+        self.workspace_status = skipped.join("\n");
+    }
+
+    /// Saves and closes the open folder, going back to eform's own forms.
+    /// Returns false if the folder couldn't be saved and is still open.
+    fn close_workspace(&mut self) -> bool {
+        if self.workspace.is_none() {
+            return true;
+        }
+        if !self.save_forms() {
+            return false;
+        }
+        self.workspace = None;
+        self.forms = std::mem::take(&mut self.local_forms);
+        self.form_index = None;
+        true
+    }
+
+    /// The folder the open forms are saved in, if they aren't kept in app storage.
+    fn forms_dir(&self) -> Option<PathBuf> {
+        self.workspace
+            .clone()
+            .or_else(crate::workspace::default_dir)
+    }
+
+    /// Writes the open forms to their folder, if they have one.
+    fn save_forms(&mut self) -> bool {
+        let Some(dir) = self.forms_dir() else {
+            return true;
+        };
+        match crate::workspace::save(&dir, &mut self.forms) {
+            Ok(()) => true,
+            Err(error) => {
+                self.workspace_status = format!("Could not save {}: {}", dir.display(), error);
+                false
+            }
+        }
+    }
+
+    /// Puts the forms kept in app storage for the folder at `dir` back among
+    /// `forms` loaded from it, in place of their older copies.
+    fn restore_unsaved(
+        &mut self,
+        dir: &Path,
+        mut forms: Vec<crate::form::Form>,
+    ) -> Vec<crate::form::Form> {
+        let Some(i) = self.unsaved.iter().position(|unsaved| unsaved.dir == dir) else {
+            return forms;
+        };
+        for (stem, mut form) in self.unsaved.remove(i).forms {
+            form.file_stem = stem;
+            let loaded = forms
+                .iter_mut()
+                .find(|loaded| form.file_stem.is_some() && loaded.file_stem == form.file_stem);
+            match loaded {
+                Some(loaded) => *loaded = form,
+                None => forms.push(form),
+            }
+        }
+        forms
+    }
+
+    /// Saves the forms once input has paused, if anything changed.
+    fn autosave(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let (time, has_input) = ctx.input(|input| (input.time, crate::widgets::can_edit(input)));
+        if has_input {
+            self.input_time.get_or_insert(time);
+        }
+        let Some(input_time) = self.input_time else {
+            return;
+        };
+        if time - input_time < AUTOSAVE_DELAY {
+            ctx.request_repaint_after(std::time::Duration::from_secs_f64(AUTOSAVE_DELAY));
+            return;
+        }
+        self.input_time = None;
+        if self.forms_dir().is_some() {
+            // Only the forms that changed are written.
+            self.save_forms();
+            return;
+        }
+        let mut changed = self.forms.len() != self.stored_forms;
+        for form in self.forms.iter_mut() {
+            let hash = form.content_hash();
+            changed |= form.saved_hash.replace(hash) != Some(hash);
+        }
+        if !changed {
+            return;
+        }
+        if let Some(storage) = frame.storage_mut() {
+            eframe::App::save(self, storage);
+            storage.flush();
+            self.stored_forms = self.forms.len();
+        }
+    }
+
+    fn restore_menu(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.group(|ui| {
-                ui.heading("Start a new form");
-                if ui.button("Blank").clicked() {
-                    self.forms.push(crate::form::Form::new());
-                    self.form_index = Some(self.forms.len() - 1);
+            ui.horizontal(|ui| {
+                if ui.button("Back").clicked() {
+                    self.backups = None;
                 }
+                ui.heading("Restore from backup");
             });
-            ui.group(|ui| {
-                ui.heading("Forms");
-                let mut delete_form = None;
-                egui::Grid::new("forms").striped(true).show(ui, |ui| {
-                    for (i, form) in self.forms.iter().enumerate() {
-                        ui.label(&form.name);
-                        if ui.button("Open").clicked() {
-                            self.form_index = Some(i);
-                        }
-                        if ui.button("❌").clicked() {
-                            delete_form = Some(i);
-                            ui.close_menu();
+            let Some(dir) = self.forms_dir() else {
+                ui.label("Backups are only kept for forms saved in a folder.");
+                return;
+            };
+            ui.label(dir.display().to_string());
+            let mut restore = None;
+            match self.backups.as_deref() {
+                Some([]) => {
+                    ui.label("No backups yet");
+                }
+                Some(backups) => {
+                    egui::Grid::new("backups").striped(true).show(ui, |ui| {
+                        for (i, backup) in backups.iter().enumerate() {
+                            ui.label(crate::time::format(backup.time));
+                            ui.label(format!("{} forms", backup.forms));
+                            if ui.button("Restore").clicked() {
+                                restore = Some(i);
+                            }
+                            ui.end_row();
                         }
-                        ui.end_row();
+                    });
+                }
+                None => {}
+            }
+            if let Some(i) = restore {
+                self.restore_backup(&dir, i);
+            }
+            if !self.workspace_status.is_empty() {
+                ui.colored_label(ui.visuals().error_fg_color, &self.workspace_status);
+            }
+        });
+    }
+
+    fn restore_backup(&mut self, dir: &std::path::Path, i: usize) {
+        if !self.save_forms() {
+            return;
+        }
+        let Some(backup) = self.backups.as_ref().and_then(|backups| backups.get(i)) else {
+            return;
+        };
+        match crate::workspace::restore(dir, backup) {
+            Ok((forms, skipped)) => {
+                self.forms = forms;
+                self.form_index = None;
+                self.backups = None;
+                self.workspace_status = skipped.join("\n");
+            }
+            Err(error) => {
+                self.workspace_status = format!("Could not restore backup: {}", error);
+            }
+        }
+    }
+
+    fn workspace_menu(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Workspace");
+        match &self.workspace {
+            Some(path) => ui.label(path.display().to_string()),
+            None => ui.label("Forms saved with eform"),
+        };
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.workspace_path).hint_text("Folder"));
+            if ui.button("Open folder…").clicked() {
+                self.open_workspace(PathBuf::from(self.workspace_path.trim()));
+            }
+            if self.workspace.is_some() && ui.button("Close folder").clicked() {
+                self.close_workspace();
+            }
+        });
+        if ui.button("Restore from backup").clicked() {
+            match self
+                .forms_dir()
+                .map(|dir| crate::workspace::list_backups(&dir))
+            {
+                Some(Err(error)) => {
+                    self.workspace_status = format!("Could not list backups: {}", error)
+                }
+                backups => self.backups = Some(backups.and_then(Result::ok).unwrap_or_default()),
+            }
+        }
+        if !self.recent_workspaces.is_empty() {
+            ui.label("Recent");
+            let mut open = None;
+            for path in self.recent_workspaces.iter() {
+                if Some(path) != self.workspace.as_ref()
+                    && ui.link(path.display().to_string()).clicked()
+                {
+                    open = Some(path.clone());
+                }
+            }
+            if let Some(path) = open {
+                self.open_workspace(path);
+            }
+        }
+        if !self.workspace_status.is_empty() {
+            ui.colored_label(ui.visuals().error_fg_color, &self.workspace_status);
+        }
+    }
+
+    fn main_menu(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.set_enabled(self.confirm.is_none());
+            ui.group(|ui| self.workspace_menu(ui));
+            ui.group(|ui| self.template_gallery(ui));
+            ui.group(|ui| self.forms_list(ui));
+            let trashed = self
+                .forms
+                .iter()
+                .filter(|form| form.trashed.is_some())
+                .count();
+            if trashed > 0 {
+                ui.group(|ui| {
+                    egui::CollapsingHeader::new(format!("Trash ({})", trashed))
+                        .id_source("trash")
+                        .show(ui, |ui| self.trash(ui));
+                });
+            }
+        });
+        if let Some(confirm) = self.confirm {
+            self.confirm_dialog(ctx, confirm);
+        }
+    }
+
+    fn template_gallery(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Start a new form");
+        let mut new_form = None;
+        let mut remove_template = None;
+        ui.horizontal_wrapped(|ui| {
+            if ui.button("Blank").clicked() {
+                new_form = Some(crate::form::Form::new());
+            }
+            for template in crate::template::built_in() {
+                if ui.button(&template.name).clicked() {
+                    new_form = Some(template.duplicate());
+                }
+            }
+            for (i, template) in self.templates.iter().enumerate() {
+                let button = ui.button(&template.name).context_menu(|ui| {
+                    if ui.button("Remove template").clicked() {
+                        remove_template = Some(i);
+                        ui.close_menu();
                     }
                 });
-                if let Some(i) = delete_form {
-                    self.forms.remove(i);
+                if button.on_hover_text("Right-click to remove").clicked() {
+                    new_form = Some(template.duplicate());
                 }
-            });
+            }
+        });
+        if let Some(i) = remove_template {
+            self.templates.remove(i);
+        }
+        if let Some(mut form) = new_form {
+            form.locale = self.locale.clone();
+            self.forms.push(form);
+            self.form_index = Some(self.forms.len() - 1);
+        }
+    }
+
+    /// The forms to list, matching the search and tag filter and in the chosen order.
+    fn shown_forms(&self) -> Vec<usize> {
+        let search = self.search.to_lowercase();
+        let show_archived = self.show_archived
+            || self
+                .tag_filter
+                .as_ref()
+                .is_some_and(|tag| tag.eq_ignore_ascii_case(crate::form::ARCHIVED_TAG));
+        let mut shown: Vec<usize> = (0..self.forms.len())
+            .filter(|&i| {
+                let form = &self.forms[i];
+                form.trashed.is_none()
+                    && (show_archived || !form.has_tag(crate::form::ARCHIVED_TAG))
+                    && self.tag_filter.as_ref().is_none_or(|tag| form.has_tag(tag))
+                    && form.name.to_lowercase().contains(&search)
+            })
+            .collect();
+        let forms = &self.forms;
+        match self.sort {
+            FormSort::Modified => shown.sort_by_key(|&i| std::cmp::Reverse(forms[i].modified)),
+            FormSort::Created => shown.sort_by_key(|&i| std::cmp::Reverse(forms[i].created)),
+            FormSort::Name => shown.sort_by_key(|&i| forms[i].name.to_lowercase()),
+            FormSort::Responses => {
+                shown.sort_by_key(|&i| std::cmp::Reverse(forms[i].responses.len()))
+            }
+        }
+        shown
+    }
+
+    fn forms_list(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Forms");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search"));
+            egui::ComboBox::from_id_source("sort")
+                .selected_text(format!("Sort by: {}", self.sort.name()))
+                .show_ui(ui, |ui| {
+                    for sort in [
+                        FormSort::Modified,
+                        FormSort::Created,
+                        FormSort::Name,
+                        FormSort::Responses,
+                    ] {
+                        ui.selectable_value(&mut self.sort, sort, sort.name());
+                    }
+                });
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Tags:");
+            if ui
+                .selectable_label(self.tag_filter.is_none(), "All")
+                .clicked()
+            {
+                self.tag_filter = None;
+            }
+            for tag in self.all_tags() {
+                let selected = self.tag_filter.as_ref() == Some(&tag);
+                if ui.selectable_label(selected, &tag).clicked() {
+                    self.tag_filter = if selected { None } else { Some(tag) };
+                }
+            }
+            ui.checkbox(&mut self.show_archived, "Show archived");
+        });
+
+        let shown = self.shown_forms();
+
+        let mut duplicate = None;
+        egui::Grid::new("forms").striped(true).show(ui, |ui| {
+            ui.strong("Name");
+            ui.strong("Modified");
+            ui.strong("Created");
+            ui.strong("Responses");
+            ui.strong("Tags");
+            ui.end_row();
+            for i in shown {
+                let form = &self.forms[i];
+                ui.label(&form.name);
+                ui.label(date_text(form.modified));
+                ui.label(date_text(form.created));
+                ui.label(form.responses.len().to_string());
+                ui.label(form.tags.join(", "));
+                if ui.button("Open").clicked() {
+                    self.form_index = Some(i);
+                }
+                if ui.button("Duplicate form").clicked() {
+                    duplicate = Some(i);
+                }
+                if ui.button("❌").on_hover_text("Move to trash").clicked() {
+                    self.confirm = Some(Confirm::Trash(i));
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = duplicate {
+            let mut form = self.forms[i].duplicate();
+            form.name = format!("Copy of {}", form.name);
+            self.forms.push(form);
+        }
+    }
+
+    /// Tags used by forms outside the trash, sorted and without repeats.
+    fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .forms
+            .iter()
+            .filter(|form| form.trashed.is_none())
+            .flat_map(|form| form.tags.iter().cloned())
+            .collect();
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        tags
+    }
+
+    fn trash(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("trash").striped(true).show(ui, |ui| {
+            for form in self.forms.iter_mut() {
+                let Some(time) = form.trashed else {
+                    continue;
+                };
+                ui.label(&form.name);
+                ui.label(format!("Deleted {}", crate::time::format(time)));
+                if ui.button("Restore").clicked() {
+                    form.trashed = None;
+                }
+                ui.end_row();
+            }
         });
+        if ui.button("Empty trash").clicked() {
+            self.confirm = Some(Confirm::EmptyTrash);
+        }
+    }
+
+    fn confirm_dialog(&mut self, ctx: &egui::Context, confirm: Confirm) {
+        let (title, message, action) = match confirm {
+            Confirm::Trash(i) => (
+                "Delete form?",
+                format!(
+                    "\"{}\" and its responses will be moved to the trash.",
+                    self.forms[i].name
+                ),
+                "Move to trash",
+            ),
+            Confirm::EmptyTrash => (
+                "Empty trash?",
+                "The forms in the trash and their responses will be deleted for good.".into(),
+                "Empty trash",
+            ),
+        };
+        let mut confirmed = false;
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(message);
+                ui.horizontal(|ui| {
+                    if ui.button(action).clicked() {
+                        confirmed = true;
+                        self.confirm = None;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.confirm = None;
+                    }
+                });
+            });
+        if !confirmed {
+            return;
+        }
+        match confirm {
+            Confirm::Trash(i) => self.forms[i].trashed = Some(crate::time::now()),
+            Confirm::EmptyTrash => self.empty_trash(),
+        }
+    }
+
+    fn empty_trash(&mut self) {
+        let dir = self.forms_dir();
+        let (trashed, forms) = std::mem::take(&mut self.forms)
+            .into_iter()
+            .partition(|form| form.trashed.is_some());
+        self.forms = forms;
+        let Some(dir) = dir else {
+            return;
+        };
+        for form in trashed {
+            if let Err(error) = crate::workspace::remove(&dir, &form) {
+                self.workspace_status = format!("Could not delete {}: {}", form.name, error);
+            }
+        }
     }
 
     fn edit_form(&mut self, ctx: &egui::Context, form_index: usize) {
+        let (undo, redo) = ctx.input_mut(|input| {
+            (
+                input.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+                input.consume_key(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::Z,
+                ),
+            )
+        });
+        if undo {
+            self.forms[form_index].undo();
+        }
+        if redo {
+            self.forms[form_index].redo();
+        }
         egui::TopBottomPanel::top("top").show(ctx, |ui| {
             ui.set_width(ui.available_width());
             ui.horizontal(|ui| {
                 if ui.button("Back").clicked() {
                     self.edit_tab = EditTab::Questions;
                     self.form_index = None;
+                    self.report_path.clear();
+                    self.report_status.clear();
+                    self.csv_path.clear();
+                    self.attachments_path.clear();
                 }
                 ui.text_edit_singleline(&mut self.forms[form_index].name);
+                let form = &mut self.forms[form_index];
+                if ui
+                    .add_enabled(form.can_undo(), egui::Button::new("Undo"))
+                    .clicked()
+                {
+                    form.undo();
+                }
+                if ui
+                    .add_enabled(form.can_redo(), egui::Button::new("Redo"))
+                    .clicked()
+                {
+                    form.redo();
+                }
+                if ui.button("Save as template").clicked() {
+                    self.templates.push(form.duplicate());
+                }
             });
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut self.edit_tab, EditTab::Questions, "Questions");
@@ -90,7 +692,7 @@ impl EformApp {
                     .selectable_value(&mut self.edit_tab, EditTab::Preview, "Preview")
                     .clicked()
                 {
-                    self.reset_form_preview(form_index);
+                    self.forms[form_index].reset_values();
                 }
                 ui.selectable_value(&mut self.edit_tab, EditTab::Responses, "Responses");
                 ui.selectable_value(&mut self.edit_tab, EditTab::Settings, "Settings");
@@ -102,71 +704,392 @@ impl EformApp {
             EditTab::Responses => self.tab_responses(ui, form_index),
             EditTab::Settings => self.tab_settings(ui, form_index),
         });
+        if let Some(form) = self.forms.get_mut(form_index) {
+            ctx.input(|input| form.record_edits(input));
+        }
     }
 
     fn tab_questions(&mut self, ui: &mut egui::Ui, form_index: usize) {
-        let mut delete_question = None;
         egui::ScrollArea::vertical()
             .auto_shrink([false; 2])
             .show(ui, |ui| {
-                for (i, question) in self.forms[form_index].questions.iter_mut().enumerate() {
-                    if question.edit(ui) {
-                        delete_question = Some(i);
-                    }
-                }
-                if ui.button("Add question").clicked() {
-                    self.forms[form_index]
-                        .questions
-                        .push(crate::question::Question::new());
-                }
-
+                crate::widgets::FormEditor::new(&mut self.forms[form_index]).show(ui);
                 ui.add_space(32.0);
             });
-        if let Some(i) = delete_question {
-            self.forms[form_index].questions.remove(i);
-        }
     }
 
     fn tab_preview(&mut self, ui: &mut egui::Ui, form_index: usize) {
         egui::ScrollArea::vertical()
             .auto_shrink([false; 2])
             .show(ui, |ui| {
-                for question in self.forms[form_index].questions.iter_mut() {
-                    question.preview(ui);
+                crate::widgets::FormFiller::new(&mut self.forms[form_index]).show(ui);
+            });
+    }
+
+    fn tab_responses(&mut self, ui: &mut egui::Ui, form_index: usize) {
+        let form = &self.forms[form_index];
+        ui.heading(format!("{} responses", form.responses.len()));
+        ui.horizontal(|ui| {
+            if self.report_path.is_empty() {
+                self.report_path = format!("{}.pdf", form.name);
+            }
+            ui.text_edit_singleline(&mut self.report_path);
+            if ui.button("Save PDF report").clicked() {
+                self.report_status =
+                    match std::fs::write(&self.report_path, crate::report::pdf_report(form)) {
+                        Ok(()) => format!("Saved {}", self.report_path),
+                        Err(error) => format!("Could not save report: {}", error),
+                    };
+            }
+        });
+        ui.horizontal(|ui| {
+            if self.csv_path.is_empty() {
+                self.csv_path = format!("{}.csv", form.name);
+            }
+            ui.text_edit_singleline(&mut self.csv_path);
+            if ui.button("Export CSV").clicked() {
+                self.report_status =
+                    match std::fs::write(&self.csv_path, crate::report::csv_export(form)) {
+                        Ok(()) => format!("Saved {}", self.csv_path),
+                        Err(error) => format!("Could not export responses: {}", error),
+                    };
+            }
+        });
+        let has_uploads = form
+            .questions()
+            .any(|question| matches!(question.kind, crate::QuestionKind::FileUpload(..)));
+        if has_uploads {
+            ui.horizontal(|ui| {
+                if self.attachments_path.is_empty() {
+                    self.attachments_path = format!("{} uploads", form.name);
                 }
-                if ui.button("Submit").clicked() {
-                    println!("{:#?}", self.forms[form_index]);
+                ui.text_edit_singleline(&mut self.attachments_path);
+                if ui.button("Save uploaded files").clicked() {
+                    let dir = std::path::Path::new(&self.attachments_path);
+                    self.report_status = match crate::report::save_attachments(form, dir) {
+                        Ok(count) => format!("Saved {} files to {}", count, self.attachments_path),
+                        Err(error) => format!("Could not save uploaded files: {}", error),
+                    };
                 }
-                if ui.button("Clear form").clicked() {
-                    self.reset_form_preview(form_index);
+            });
+        }
+        if !self.report_status.is_empty() {
+            ui.label(&self.report_status);
+        }
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                for question in form.questions() {
+                    question.summarize(&form.responses, &form.locale).show(ui);
                 }
             });
     }
 
-    fn reset_form_preview(&mut self, form_index: usize) {
-        for question in self.forms[form_index].questions.iter_mut() {
-            question.reset_value();
+    fn tab_settings(&mut self, ui: &mut egui::Ui, form_index: usize) {
+        let all_tags = self.all_tags();
+        let form = &mut self.forms[form_index];
+        ui.heading("Tags");
+        let mut remove_tag = None;
+        ui.horizontal_wrapped(|ui| {
+            for tag in form.tags.iter() {
+                ui.group(|ui| {
+                    ui.label(tag);
+                    if ui.small_button("❌").clicked() {
+                        remove_tag = Some(tag.clone());
+                    }
+                });
+            }
+        });
+        if let Some(tag) = remove_tag {
+            form.remove_tag(&tag);
+        }
+        ui.horizontal(|ui| {
+            let response =
+                ui.add(egui::TextEdit::singleline(&mut self.new_tag).hint_text("New tag"));
+            let entered =
+                response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            if ui.button("Add tag").clicked() || entered {
+                form.add_tag(&self.new_tag);
+                self.new_tag.clear();
+            }
+        });
+        let suggestions: Vec<&String> = all_tags.iter().filter(|tag| !form.has_tag(tag)).collect();
+        if !suggestions.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Other tags:");
+                for tag in suggestions {
+                    if ui.button(tag).clicked() {
+                        form.add_tag(tag);
+                    }
+                }
+            });
+        }
+        let archived = form.has_tag(crate::form::ARCHIVED_TAG);
+        if ui
+            .button(if archived { "Unarchive" } else { "Archive" })
+            .on_hover_text("Archived forms are hidden from the forms list by default")
+            .clicked()
+        {
+            if archived {
+                form.remove_tag(crate::form::ARCHIVED_TAG);
+            } else {
+                form.add_tag(crate::form::ARCHIVED_TAG);
+            }
         }
-    }
-
-    fn tab_responses(&mut self, ui: &mut egui::Ui, _: usize) {
-        ui.heading("UNDER CONSTRUCTION");
-    }
 
-    fn tab_settings(&mut self, ui: &mut egui::Ui, _: usize) {
-        ui.heading("UNDER CONSTRUCTION");
+        ui.separator();
+        ui.heading("Dates and times");
+        ui.horizontal(|ui| {
+            for (name, preset) in crate::Locale::presets() {
+                if ui.selectable_label(form.locale == preset, name).clicked() {
+                    form.locale = preset;
+                }
+            }
+        });
+        egui::ComboBox::from_label("Date order")
+            .selected_text(form.locale.date_order.name())
+            .show_ui(ui, |ui| {
+                for order in crate::DateOrder::ALL {
+                    ui.selectable_value(&mut form.locale.date_order, order, order.name());
+                }
+            });
+        ui.checkbox(&mut form.locale.twenty_four_hour, "24-hour clock");
+        if ui
+            .add_enabled(
+                form.locale != self.locale,
+                egui::Button::new("Use for new forms"),
+            )
+            .clicked()
+        {
+            self.locale = form.locale.clone();
+        }
     }
 }
 
 impl eframe::App for EformApp {
-    fn update(&mut self, ctx: &egui::Context, _: &mut eframe::Frame) {
-        match self.form_index {
-            None => self.main_menu(ctx),
-            Some(form_index) => self.edit_form(ctx, form_index),
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if self.backups.is_some() {
+            self.restore_menu(ctx);
+        } else {
+            match self.form_index {
+                None => self.main_menu(ctx),
+                Some(form_index) => self.edit_form(ctx, form_index),
+            }
         }
+        self.autosave(ctx, frame);
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        if self.forms_dir().is_none() {
+            eframe::set_value(storage, "data", self);
+            return;
+        }
+        // Keep forms that are saved in a folder out of app storage, unless the
+        // folder couldn't be written, so that they aren't lost.
+        let saved = self.save_forms();
+        if let Some(dir) = self.forms_dir() {
+            self.unsaved.retain(|unsaved| unsaved.dir != dir);
+            if !saved {
+                let forms = self
+                    .forms
+                    .iter()
+                    .filter(|form| {
+                        form.file_stem.is_none() || form.saved_hash != Some(form.content_hash())
+                    })
+                    .map(|form| (form.file_stem.clone(), form.clone()))
+                    .collect();
+                self.unsaved.push(UnsavedForms { dir, forms });
+            }
+        }
+        let mut stored = if crate::workspace::default_dir().is_some() {
+            vec![]
+        } else {
+            std::mem::take(&mut self.local_forms)
+        };
+        std::mem::swap(&mut self.forms, &mut stored);
         eframe::set_value(storage, "data", self);
+        std::mem::swap(&mut self.forms, &mut stored);
+        if crate::workspace::default_dir().is_none() {
+            self.local_forms = stored;
+        }
+    }
+}
+
+/// A form's timestamp for the forms list, blank for forms saved before it was
+/// kept.
+fn date_text(time: u64) -> String {
+    if time == 0 {
+        String::new()
+    } else {
+        crate::time::format(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form::Form;
+    use crate::workspace::tests::TempDir;
+
+    #[test]
+    fn emptying_trash_deletes_only_trashed_forms() {
+        let dir = TempDir::new("empty-trash");
+        let mut app = EformApp {
+            workspace: Some(dir.0.clone()),
+            ..Default::default()
+        };
+        for name in ["Kept", "Trashed"] {
+            let mut form = Form::new();
+            form.name = name.into();
+            app.forms.push(form);
+        }
+        assert!(app.save_forms());
+        app.forms[1].trashed = Some(1);
+        app.empty_trash();
+
+        assert_eq!(app.forms.len(), 1);
+        assert_eq!(app.forms[0].name, "Kept");
+        assert!(dir.0.join("Kept.form.json").exists());
+        assert!(!dir.0.join("Trashed.form.json").exists());
+        assert!(!dir.0.join("Trashed.responses.json").exists());
+    }
+
+    fn app_with(forms: &[(&str, u64, u64, usize)]) -> EformApp {
+        let mut app = EformApp::default();
+        for &(name, created, modified, responses) in forms {
+            let mut form = Form::new();
+            form.name = name.into();
+            form.created = created;
+            form.modified = modified;
+            for _ in 0..responses {
+                form.submit();
+            }
+            app.forms.push(form);
+        }
+        app
+    }
+
+    #[test]
+    fn search_matches_names_ignoring_case() {
+        let mut app = app_with(&[
+            ("Team lunch", 1, 1, 0),
+            ("Quiz", 2, 2, 0),
+            ("LUNCH poll", 3, 3, 0),
+        ]);
+        app.forms[2].trashed = Some(4);
+        app.search = "lunch".into();
+        assert_eq!(app.shown_forms(), [0]);
+        app.forms[2].trashed = None;
+        app.sort = FormSort::Name;
+        assert_eq!(app.shown_forms(), [2, 0]);
+    }
+
+    #[test]
+    fn sorts_forms() {
+        let mut app = app_with(&[("b", 1, 5, 2), ("C", 3, 4, 0), ("a", 2, 6, 1)]);
+        app.sort = FormSort::Modified;
+        assert_eq!(app.shown_forms(), [2, 0, 1]);
+        app.sort = FormSort::Created;
+        assert_eq!(app.shown_forms(), [1, 2, 0]);
+        app.sort = FormSort::Name;
+        assert_eq!(app.shown_forms(), [2, 0, 1]);
+        app.sort = FormSort::Responses;
+        assert_eq!(app.shown_forms(), [0, 2, 1]);
+    }
+
+    #[test]
+    fn filters_by_tag_and_hides_archived_forms() {
+        let mut app = app_with(&[("a", 1, 3, 0), ("b", 1, 2, 0), ("c", 1, 1, 0)]);
+        app.forms[0].add_tag("Work");
+        app.forms[1].add_tag("work");
+        app.forms[1].add_tag(crate::form::ARCHIVED_TAG);
+        app.forms[2].add_tag("Home");
+        assert_eq!(app.shown_forms(), [0, 2]);
+        assert_eq!(app.all_tags(), ["Archived", "Home", "Work"]);
+
+        app.tag_filter = Some("WORK".into());
+        assert_eq!(app.shown_forms(), [0]);
+        app.show_archived = true;
+        assert_eq!(app.shown_forms(), [0, 1]);
+
+        // Picking the archived tag shows archived forms without the checkbox.
+        app.show_archived = false;
+        app.tag_filter = Some(crate::form::ARCHIVED_TAG.into());
+        assert_eq!(app.shown_forms(), [1]);
+    }
+
+    /// App storage kept in memory.
+    #[derive(Default)]
+    struct MemoryStorage(std::collections::HashMap<String, String>);
+
+    impl eframe::Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.into(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    #[test]
+    fn keeps_forms_of_unwritable_folders_apart() {
+        let dir = TempDir::new("unwritable-folder");
+        // A file where the folder should be, so that it can't be written.
+        let workspace = dir.0.join("workspace");
+        std::fs::write(&workspace, "").unwrap();
+        let mut app = EformApp {
+            workspace: Some(workspace.clone()),
+            ..Default::default()
+        };
+        app.forms.push(Form::new());
+        let mut storage = MemoryStorage::default();
+        eframe::App::save(&mut app, &mut storage);
+
+        let stored: EformApp = eframe::get_value(&storage, "data").unwrap();
+        assert!(stored.forms.is_empty());
+        assert_eq!(stored.unsaved.len(), 1);
+        assert_eq!(stored.unsaved[0].dir, workspace);
+        assert_eq!(stored.unsaved[0].forms.len(), 1);
+    }
+
+    #[test]
+    fn restores_unsaved_forms_into_their_folder() {
+        let dir = TempDir::new("restore-unsaved");
+        let mut forms = vec![Form::new(), Form::new()];
+        forms[0].name = "a".into();
+        forms[1].name = "b".into();
+        crate::workspace::save(&dir.0, &mut forms).unwrap();
+
+        let mut edited = forms[0].clone();
+        edited.description = "Edited".into();
+        let mut new = Form::new();
+        new.name = "c".into();
+        let mut app = EformApp {
+            unsaved: vec![
+                UnsavedForms {
+                    dir: dir.0.join("other"),
+                    forms: vec![(None, Form::new())],
+                },
+                UnsavedForms {
+                    dir: dir.0.clone(),
+                    forms: vec![(Some("a".into()), edited), (None, new)],
+                },
+            ],
+            ..Default::default()
+        };
+        let (loaded, _) = crate::workspace::load(&dir.0).unwrap();
+        let restored = app.restore_unsaved(&dir.0, loaded);
+
+        let names: Vec<_> = restored.iter().map(|form| form.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(restored[0].description, "Edited");
+        assert_eq!(restored[0].file_stem.as_deref(), Some("a"));
+        assert_eq!(restored[2].file_stem, None);
+        // Forms of other folders stay until those are opened.
+        assert_eq!(app.unsaved.len(), 1);
+        assert_eq!(app.unsaved[0].dir, dir.0.join("other"));
     }
 }
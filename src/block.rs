@@ -0,0 +1,88 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::question::QuestionAction;
+
+/// Content shown between questions that has nothing to answer, so it is left
+/// out of responses and reports.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Block {
+    /// A heading with Markdown text under it.
+    Text {
+        title: String,
+        text: String,
+    },
+    Image {
+        image: Option<crate::image::Image>,
+        caption: String,
+    },
+    Divider,
+}
+
+impl Block {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Text { .. } => "Title and description",
+            Self::Image { .. } => "Image",
+            Self::Divider => "Divider",
+        }
+    }
+
+    pub fn edit(
+        &mut self,
+        ui: &mut egui::Ui,
+        is_first: bool,
+        is_last: bool,
+    ) -> Option<QuestionAction> {
+        let mut action = None;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(self.name());
+                action = crate::question::item_buttons(ui, is_first, is_last);
+            });
+            match self {
+                Self::Text { title, text } => {
+                    ui.add(egui::TextEdit::singleline(title).hint_text("Title"));
+                    ui.add(
+                        egui::TextEdit::multiline(text)
+                            .hint_text("Description (optional)")
+                            .desired_rows(1),
+                    );
+                }
+                Self::Image { image, caption } => {
+                    crate::image::edit(ui, image, ui.available_width());
+                    ui.add(egui::TextEdit::singleline(caption).hint_text("Caption (optional)"));
+                }
+                Self::Divider => {
+                    ui.separator();
+                }
+            }
+        });
+        action
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        match self {
+            Self::Text { title, text } => {
+                ui.group(|ui| {
+                    ui.set_width(ui.available_width());
+                    ui.heading(title);
+                    if !text.is_empty() {
+                        crate::markdown::show(ui, text);
+                    }
+                });
+            }
+            Self::Image { image, caption } => {
+                if let Some(image) = image {
+                    image.show(ui, ui.available_width());
+                }
+                if !caption.is_empty() {
+                    ui.label(caption);
+                }
+            }
+            Self::Divider => {
+                ui.separator();
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! A month calendar for picking dates.
+
+use eframe::egui;
+
+use crate::locale::Locale;
+use crate::question::value_types::DateValue;
+use crate::time::{days_in_month, weekday};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// A button showing `date` that opens a calendar to pick a day from, with days
+/// outside `min` to `max` disabled, written in `locale`. Without the year, the
+/// calendar only pages through months. Returns whether a day was picked.
+pub fn date_picker(
+    ui: &mut egui::Ui,
+    date: &mut DateValue,
+    include_year: bool,
+    min: Option<&DateValue>,
+    max: Option<&DateValue>,
+    locale: &Locale,
+) -> bool {
+    let label = if date.is_empty() {
+        "Choose a date".into()
+    } else {
+        locale.format_date(date, include_year)
+    };
+    let button = ui.button(format!("📅 {}", label));
+    let popup_id = button.id.with("calendar");
+    if button.clicked() {
+        let start = if date.is_empty() {
+            first_shown(include_year, min, max)
+        } else {
+            (date.year.max(1), date.month)
+        };
+        ui.data_mut(|data| data.insert_temp(popup_id, start));
+        ui.memory_mut(|memory| memory.toggle_popup(popup_id));
+    }
+    if !ui.memory(|memory| memory.is_popup_open(popup_id)) {
+        return false;
+    }
+
+    // The year and month the calendar is showing.
+    let (mut year, month) = ui
+        .data_mut(|data| data.get_temp::<(usize, usize)>(popup_id))
+        .unwrap_or_else(|| first_shown(include_year, min, max));
+    // Saved answers and limits can have any month, so keep to a real one.
+    let mut month = month.clamp(1, 12);
+    let mut picked = false;
+    let area = egui::Area::new(popup_id)
+        .order(egui::Order::Foreground)
+        .constrain(true)
+        .fixed_pos(button.rect.left_bottom())
+        .show(ui.ctx(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.small_button("⏴").clicked() {
+                        if month == 1 {
+                            month = 12;
+                            year = year.saturating_sub(1).max(1);
+                        } else {
+                            month -= 1;
+                        }
+                    }
+                    ui.label(MONTHS[month - 1]);
+                    if include_year {
+                        ui.add(egui::DragValue::new(&mut year).clamp_range(1..=9999));
+                    }
+                    if ui.small_button("⏵").clicked() {
+                        if month == 12 {
+                            month = 1;
+                            year = (year + 1).min(9999);
+                        } else {
+                            month += 1;
+                        }
+                    }
+                });
+                // Without a year, February gets its leap day and the days
+                // aren't lined up under weekdays.
+                let layout_year = if include_year { year as i64 } else { 2000 };
+                egui::Grid::new(popup_id.with("days")).show(ui, |ui| {
+                    let mut column = 0;
+                    if include_year {
+                        for name in WEEKDAYS {
+                            ui.label(name);
+                        }
+                        ui.end_row();
+                        column = weekday(layout_year, month as u32, 1);
+                        for _ in 0..column {
+                            ui.label("");
+                        }
+                    }
+                    for day in 1..=days_in_month(layout_year, month as u32) as usize {
+                        let candidate = DateValue {
+                            year: if include_year { year } else { 0 },
+                            month,
+                            day,
+                            time: date.time.clone(),
+                        };
+                        let key = candidate.sort_key(include_year);
+                        let allowed = min.is_none_or(|min| key >= min.sort_key(include_year))
+                            && max.is_none_or(|max| key <= max.sort_key(include_year));
+                        let selected = date.sort_key(include_year) == key;
+                        let label = egui::SelectableLabel::new(selected, day.to_string());
+                        if ui.add_enabled(allowed, label).clicked() {
+                            *date = candidate;
+                            picked = true;
+                        }
+                        column += 1;
+                        if column == 7 {
+                            ui.end_row();
+                            column = 0;
+                        }
+                    }
+                });
+            });
+        });
+    ui.data_mut(|data| data.insert_temp(popup_id, (year, month)));
+    let clicked_away = area.response.clicked_elsewhere() && !button.clicked();
+    if picked || clicked_away || ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+        ui.memory_mut(|memory| memory.close_popup());
+    }
+    picked
+}
+
+/// The month to open the calendar at when no date is picked yet: the current
+/// month, moved into the range of dates that can be picked.
+fn first_shown(
+    include_year: bool,
+    min: Option<&DateValue>,
+    max: Option<&DateValue>,
+) -> (usize, usize) {
+    let today = DateValue::today();
+    let key = today.sort_key(include_year);
+    let start = match (min, max) {
+        (Some(min), _) if key < min.sort_key(include_year) => min,
+        (_, Some(max)) if key > max.sort_key(include_year) => max,
+        _ => &today,
+    };
+    (start.year.max(1), start.month.clamp(1, 12))
+}
@@ -1,16 +1,481 @@
+use std::collections::HashMap;
+
+use eframe::egui;
 use serde::{Deserialize, Serialize};
 
+use crate::question::Question;
+
+mod builder;
+mod item;
+
+pub use builder::{FormBuilder, OptionList};
+pub use item::FormItem;
+
+/// Tag that hides a form from the forms list unless archived forms are shown.
+pub const ARCHIVED_TAG: &str = "Archived";
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "SavedForm")]
 pub struct Form {
     pub name: String,
-    pub questions: Vec<crate::question::Question>,
+    /// Shown above the questions, in Markdown.
+    pub description: String,
+    /// The questions and blocks of the form, in order.
+    pub items: Vec<FormItem>,
+    pub responses: Vec<crate::response::Response>,
+    next_question_id: usize,
+    /// When the form was created, in seconds since the Unix epoch, or 0 if it
+    /// was saved before forms kept track.
+    pub created: u64,
+    /// When the form's name or items were last edited, like `created`.
+    pub modified: u64,
+    /// Labels for grouping forms, like "HR" or "Events".
+    pub tags: Vec<String>,
+    /// When the form was moved to the trash, in seconds since the Unix epoch.
+    pub trashed: Option<u64>,
+    /// How dates and times are written in the form and its reports.
+    pub locale: crate::locale::Locale,
+    #[serde(skip)]
+    history: crate::history::History<FormEdit>,
+    /// When `record_edits` last looked for changes.
+    #[serde(skip)]
+    recorded_time: Option<f64>,
+    #[serde(skip)]
+    errors: HashMap<usize, String>,
+    /// Name of the form's files in its workspace folder, without extension.
+    #[serde(skip)]
+    pub(crate) file_stem: Option<String>,
+    /// `content_hash` as of the last save, or `None` if the form was never saved.
+    #[serde(skip)]
+    pub(crate) saved_hash: Option<u64>,
+}
+
+/// How a form is loaded, accepting saves from before forms had blocks, when
+/// the items were all questions.
+#[derive(Deserialize)]
+struct SavedForm {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    items: Vec<FormItem>,
+    #[serde(default)]
+    questions: Vec<Question>,
+    #[serde(default)]
+    responses: Vec<crate::response::Response>,
+    #[serde(default)]
+    next_question_id: usize,
+    #[serde(default)]
+    created: u64,
+    #[serde(default)]
+    modified: u64,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    trashed: Option<u64>,
+    #[serde(default)]
+    locale: crate::locale::Locale,
+}
+
+impl From<SavedForm> for Form {
+    fn from(saved: SavedForm) -> Self {
+        let mut form = Self {
+            name: saved.name,
+            description: saved.description,
+            items: saved.items,
+            responses: saved.responses,
+            next_question_id: saved.next_question_id,
+            created: saved.created,
+            modified: saved.modified,
+            tags: saved.tags,
+            trashed: saved.trashed,
+            locale: saved.locale,
+            history: Default::default(),
+            recorded_time: None,
+            errors: HashMap::new(),
+            file_stem: None,
+            saved_hash: None,
+        };
+        form.items
+            .extend(saved.questions.into_iter().map(FormItem::Question));
+        form.assign_question_ids();
+        form
+    }
+}
+
+/// The parts of a form covered by undo and redo.
+#[derive(Clone, Debug, PartialEq)]
+struct FormEdit {
+    name: String,
+    description: String,
+    items: Vec<FormItem>,
 }
 
 impl Form {
     pub fn new() -> Self {
+        let now = crate::time::now();
         Self {
             name: "Untitled form".into(),
-            questions: vec![],
+            description: String::new(),
+            items: vec![],
+            responses: vec![],
+            next_question_id: 0,
+            created: now,
+            modified: now,
+            tags: vec![],
+            trashed: None,
+            locale: Default::default(),
+            history: Default::default(),
+            recorded_time: None,
+            errors: HashMap::new(),
+            file_stem: None,
+            saved_hash: None,
+        }
+    }
+
+    /// Starts building a form in code.
+    pub fn builder(name: impl Into<String>) -> FormBuilder {
+        FormBuilder::new(name)
+    }
+
+    /// A new form with the same name, tags and items, without the responses.
+    pub fn duplicate(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            items: self.items.clone(),
+            tags: self.tags.clone(),
+            locale: self.locale.clone(),
+            next_question_id: self.next_question_id,
+            ..Self::new()
+        }
+    }
+
+    /// A hash of everything saved about the form, to tell whether it changed
+    /// without keeping a copy of it.
+    pub(crate) fn content_hash(&self) -> u64 {
+        let mut writer = HashWriter(std::collections::hash_map::DefaultHasher::new());
+        // Writing to a hasher can't fail, and forms always serialize.
+        let _ = serde_json::to_writer(&mut writer, self);
+        std::hash::Hasher::finish(&writer.0)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Adds `tag` unless the form already has it, ignoring case.
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        if !tag.is_empty() && !self.has_tag(tag) {
+            self.tags.push(tag.to_owned());
+        }
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+    }
+
+    pub fn new_question_id(&mut self) -> usize {
+        self.next_question_id += 1;
+        self.next_question_id
+    }
+
+    pub fn new_question(&mut self) -> Question {
+        Question::new(self.new_question_id())
+    }
+
+    /// The questions among the form's items, skipping blocks.
+    pub fn questions(&self) -> impl Iterator<Item = &Question> {
+        self.items.iter().filter_map(FormItem::question)
+    }
+
+    pub fn questions_mut(&mut self) -> impl Iterator<Item = &mut Question> {
+        self.items.iter_mut().filter_map(FormItem::question_mut)
+    }
+
+    /// Gives an id to questions saved before questions had one.
+    fn assign_question_ids(&mut self) {
+        for i in 0..self.items.len() {
+            if self.items[i]
+                .question()
+                .is_some_and(|question| question.id == 0)
+            {
+                let id = self.new_question_id();
+                if let Some(question) = self.items[i].question_mut() {
+                    question.id = id;
+                }
+            }
+        }
+    }
+
+    /// Carries out `action` on the item at `i`, as requested from its editor.
+    pub fn apply_action(&mut self, i: usize, action: crate::question::QuestionAction) {
+        use crate::question::QuestionAction;
+        match action {
+            QuestionAction::MoveUp if i > 0 && i < self.items.len() => self.items.swap(i - 1, i),
+            QuestionAction::MoveDown if i + 1 < self.items.len() => self.items.swap(i, i + 1),
+            QuestionAction::Duplicate if i < self.items.len() => {
+                let mut item = self.items[i].clone();
+                if let FormItem::Question(question) = &mut item {
+                    question.id = self.new_question_id();
+                }
+                self.items.insert(i + 1, item);
+            }
+            QuestionAction::InsertBelow if i < self.items.len() => {
+                let question = self.new_question();
+                self.items.insert(i + 1, FormItem::Question(question));
+            }
+            QuestionAction::Delete if i < self.items.len() => {
+                self.items.remove(i);
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks the answers currently entered, returning the messages for
+    /// questions that can't be submitted as they are.
+    pub fn validate(&self) -> HashMap<usize, String> {
+        self.questions()
+            .filter_map(|question| {
+                question
+                    .validate(&self.locale)
+                    .err()
+                    .map(|error| (question.id, error))
+            })
+            .collect()
+    }
+
+    /// The message from the last submit attempt for the question with `id`.
+    pub fn error(&self, id: usize) -> Option<&str> {
+        self.errors.get(&id).map(|error| error.as_str())
+    }
+
+    /// Records the answers currently entered as a response and clears them,
+    /// unless some don't validate. Returns whether a response was recorded.
+    pub fn submit(&mut self) -> bool {
+        self.errors = self.validate();
+        if !self.errors.is_empty() {
+            return false;
+        }
+        let answers = self
+            .questions()
+            .map(|question| (question.id, question.kind.value()))
+            .collect();
+        self.responses.push(crate::response::Response { answers });
+        self.reset_values();
+        true
+    }
+
+    /// Clears the answers currently entered and any errors shown for them.
+    pub fn reset_values(&mut self) {
+        self.errors.clear();
+        for question in self.questions_mut() {
+            question.reset_value();
         }
     }
+
+    fn edit_state(&self) -> FormEdit {
+        let mut items = self.items.clone();
+        for question in items.iter_mut().filter_map(FormItem::question_mut) {
+            question.reset_value();
+        }
+        FormEdit {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            items,
+        }
+    }
+
+    /// Adds any changes made since the last call to the undo history. Only the
+    /// first call in a frame with input looks for changes, since edits come
+    /// from input.
+    pub fn record_edits(&mut self, input: &egui::InputState) {
+        if self.recorded_time.is_some()
+            && (self.recorded_time == Some(input.time) || !crate::widgets::can_edit(input))
+        {
+            return;
+        }
+        self.recorded_time = Some(input.time);
+        let state = self.edit_state();
+        if self.history.feed(input.time, state) {
+            self.modified = crate::time::now();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(edit) = self.history.undo(self.edit_state()) {
+            self.name = edit.name;
+            self.description = edit.description;
+            self.items = edit.items;
+            self.modified = crate::time::now();
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(edit) = self.history.redo(self.edit_state()) {
+            self.name = edit.name;
+            self.description = edit.description;
+            self.items = edit.items;
+            self.modified = crate::time::now();
+        }
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Feeds whatever is written to it into a hasher.
+struct HashWriter<H>(H);
+
+impl<H: std::hash::Hasher> std::io::Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::question::QuestionAction;
+
+    /// A form with questions named after `names`.
+    fn form_with(names: &[&str]) -> Form {
+        let mut form = Form::new();
+        for name in names {
+            let mut question = form.new_question();
+            question.name = name.to_string();
+            form.items.push(FormItem::Question(question));
+        }
+        form
+    }
+
+    fn names(form: &Form) -> Vec<&str> {
+        form.questions()
+            .map(|question| question.name.as_str())
+            .collect()
+    }
+
+    fn question_names(form: &Form) -> Vec<(usize, &str)> {
+        form.questions()
+            .map(|question| (question.id, question.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn tags_ignore_case_and_whitespace() {
+        let mut form = Form::new();
+        form.add_tag(" Work ");
+        form.add_tag("work");
+        form.add_tag("  ");
+        assert_eq!(form.tags, ["Work"]);
+        assert!(form.has_tag("WORK"));
+        form.remove_tag("wOrK");
+        assert!(form.tags.is_empty());
+    }
+
+    #[test]
+    fn moves_questions() {
+        let mut form = form_with(&["a", "b", "c"]);
+        form.apply_action(2, QuestionAction::MoveUp);
+        assert_eq!(names(&form), ["a", "c", "b"]);
+        form.apply_action(0, QuestionAction::MoveDown);
+        assert_eq!(names(&form), ["c", "a", "b"]);
+        // Moving past either end does nothing.
+        form.apply_action(0, QuestionAction::MoveUp);
+        form.apply_action(2, QuestionAction::MoveDown);
+        assert_eq!(names(&form), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn duplicates_and_inserts_questions() {
+        let mut form = form_with(&["a", "b"]);
+        form.apply_action(0, QuestionAction::Duplicate);
+        assert_eq!(names(&form), ["a", "a", "b"]);
+        form.apply_action(2, QuestionAction::InsertBelow);
+        assert_eq!(form.items.len(), 4);
+        assert_eq!(names(&form)[..3], ["a", "a", "b"]);
+        let mut ids: Vec<_> = form.questions().map(|question| question.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+    }
+
+    #[test]
+    fn duplicates_blocks() {
+        let mut form = form_with(&["a"]);
+        form.items
+            .push(FormItem::Block(crate::block::Block::Divider));
+        form.apply_action(1, QuestionAction::Duplicate);
+        assert_eq!(form.items.len(), 3);
+        assert!(matches!(
+            form.items[2],
+            FormItem::Block(crate::block::Block::Divider)
+        ));
+        assert_eq!(names(&form), ["a"]);
+    }
+
+    #[test]
+    fn deletes_questions() {
+        let mut form = form_with(&["a", "b", "c"]);
+        form.apply_action(1, QuestionAction::Delete);
+        assert_eq!(names(&form), ["a", "c"]);
+        form.apply_action(5, QuestionAction::Delete);
+        assert_eq!(names(&form), ["a", "c"]);
+    }
+
+    #[test]
+    fn loads_forms_saved_before_blocks() {
+        // eframe keeps app storage as RON.
+        let ron = r#"(
+            name: "Survey",
+            questions: [
+                (name: "Name", config: ShortAnswer(()), value: ShortAnswer((text: ""))),
+                (name: "Comments", config: Paragraph(()), value: Paragraph((text: ""))),
+            ],
+        )"#;
+        let form: Form = ron::from_str(ron).unwrap();
+        assert_eq!(form.name, "Survey");
+        assert_eq!(form.items.len(), 2);
+        assert_eq!(question_names(&form), [(1, "Name"), (2, "Comments")]);
+        assert_eq!(form.created, 0);
+        assert!(form.responses.is_empty());
+    }
+
+    #[test]
+    fn keeps_ids_of_saved_questions() {
+        let json = r#"{
+            "name": "Survey",
+            "items": [
+                { "Question": { "id": 4, "name": "Name", "config": { "ShortAnswer": null },
+                    "value": { "ShortAnswer": { "text": "" } } } },
+                { "Block": "Divider" }
+            ],
+            "questions": [
+                { "name": "Email", "config": { "ShortAnswer": null },
+                    "value": { "ShortAnswer": { "text": "" } } }
+            ],
+            "next_question_id": 4
+        }"#;
+        let mut form: Form = serde_json::from_str(json).unwrap();
+        assert_eq!(form.items.len(), 3);
+        assert_eq!(question_names(&form), [(4, "Name"), (5, "Email")]);
+        assert_eq!(form.new_question_id(), 6);
+    }
 }
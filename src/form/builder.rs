@@ -0,0 +1,231 @@
+use crate::block::Block;
+use crate::question::config_types::*;
+use crate::question::{Question, QuestionConfig, QuestionKind};
+
+use super::{Form, FormItem};
+
+/// Builds a form in code, one question at a time.
+///
+/// ```
+/// let form = eform::Form::builder("Event RSVP")
+///     .short_answer("Name")
+///     .multiple_choice("Attending?", ["Yes", "No", "Maybe"])
+///     .build();
+/// assert_eq!(form.questions().count(), 2);
+/// ```
+pub struct FormBuilder {
+    form: Form,
+}
+
+impl FormBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        let mut form = Form::new();
+        form.name = name.into();
+        Self { form }
+    }
+
+    /// Sets the text shown above the questions, in Markdown.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.form.description = description.into();
+        self
+    }
+
+    /// Sets the help text of the question added last, in Markdown.
+    pub fn question_description(mut self, description: impl Into<String>) -> Self {
+        if let Some(question) = self.form.questions_mut().last() {
+            question.description = description.into();
+        }
+        self
+    }
+
+    /// Sets the picture shown with the question added last.
+    pub fn question_image(mut self, image: crate::image::Image) -> Self {
+        if let Some(question) = self.form.questions_mut().last() {
+            question.image = Some(image);
+        }
+        self
+    }
+
+    /// Adds a question of any type, including registered custom types.
+    pub fn question(mut self, name: impl Into<String>, config: QuestionConfig) -> Self {
+        let question = Question {
+            id: self.form.new_question_id(),
+            name: name.into(),
+            description: String::new(),
+            image: None,
+            kind: QuestionKind::new(config),
+        };
+        self.form.items.push(FormItem::Question(question));
+        self
+    }
+
+    /// Adds a block of content with no answer.
+    pub fn block(mut self, block: Block) -> Self {
+        self.form.items.push(FormItem::Block(block));
+        self
+    }
+
+    pub fn text_block(self, title: impl Into<String>, text: impl Into<String>) -> Self {
+        self.block(Block::Text {
+            title: title.into(),
+            text: text.into(),
+        })
+    }
+
+    pub fn divider(self) -> Self {
+        self.block(Block::Divider)
+    }
+
+    pub fn short_answer(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::ShortAnswer(ShortAnswerConfig))
+    }
+
+    pub fn paragraph(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::Paragraph(ParagraphConfig))
+    }
+
+    pub fn multiple_choice(self, name: impl Into<String>, options: impl Into<OptionList>) -> Self {
+        let options = options.into().0;
+        self.question(
+            name,
+            QuestionConfig::MultipleChoice(MultipleChoiceConfig {
+                options,
+                ..Default::default()
+            }),
+        )
+    }
+
+    pub fn checkboxes(self, name: impl Into<String>, options: impl Into<OptionList>) -> Self {
+        let options = options.into().0;
+        self.question(
+            name,
+            QuestionConfig::Checkboxes(CheckboxesConfig {
+                options,
+                ..Default::default()
+            }),
+        )
+    }
+
+    pub fn dropdown(self, name: impl Into<String>, options: impl Into<OptionList>) -> Self {
+        let options = options.into().0;
+        self.question(name, QuestionConfig::Dropdown(DropdownConfig { options }))
+    }
+
+    pub fn linear_scale(self, name: impl Into<String>, start: i64, end: i64) -> Self {
+        self.question(
+            name,
+            QuestionConfig::LinearScale(LinearScaleConfig {
+                start,
+                end,
+                ..Default::default()
+            }),
+        )
+    }
+
+    /// Adds a 0 to 10 "how likely are you to recommend" question, summed up
+    /// as a Net Promoter Score.
+    pub fn net_promoter_score(self, name: impl Into<String>) -> Self {
+        self.question(
+            name,
+            QuestionConfig::LinearScale(LinearScaleConfig::net_promoter_score()),
+        )
+    }
+
+    pub fn multiple_choice_grid(
+        self,
+        name: impl Into<String>,
+        rows: impl Into<OptionList>,
+        columns: impl Into<OptionList>,
+    ) -> Self {
+        let (rows, columns) = (rows.into().0, columns.into().0);
+        self.question(
+            name,
+            QuestionConfig::MultipleChoiceGrid(MultipleChoiceGridConfig { rows, columns }),
+        )
+    }
+
+    pub fn checkbox_grid(
+        self,
+        name: impl Into<String>,
+        rows: impl Into<OptionList>,
+        columns: impl Into<OptionList>,
+    ) -> Self {
+        let (rows, columns) = (rows.into().0, columns.into().0);
+        self.question(
+            name,
+            QuestionConfig::CheckboxGrid(CheckboxGridConfig { rows, columns }),
+        )
+    }
+
+    pub fn date(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::Date(DateConfig::default()))
+    }
+
+    pub fn time(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::Time(TimeConfig::default()))
+    }
+
+    /// Adds a number question, taking whole numbers only if `integer`.
+    pub fn number(self, name: impl Into<String>, integer: bool) -> Self {
+        self.question(
+            name,
+            QuestionConfig::Number(NumberConfig {
+                integer,
+                ..Default::default()
+            }),
+        )
+    }
+
+    pub fn email(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::Email(EmailConfig))
+    }
+
+    /// Adds a phone number question, with `country_code` filled in to start.
+    pub fn phone(self, name: impl Into<String>, country_code: impl Into<String>) -> Self {
+        self.question(
+            name,
+            QuestionConfig::Phone(PhoneConfig {
+                default_country_code: country_code.into(),
+            }),
+        )
+    }
+
+    pub fn url(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::Url(UrlConfig))
+    }
+
+    pub fn address(self, name: impl Into<String>) -> Self {
+        self.question(name, QuestionConfig::Address(AddressConfig))
+    }
+
+    /// Adds a file upload question taking up to `max_files` files of any type.
+    pub fn file_upload(self, name: impl Into<String>, max_files: usize) -> Self {
+        self.question(
+            name,
+            QuestionConfig::FileUpload(FileUploadConfig {
+                max_files,
+                ..Default::default()
+            }),
+        )
+    }
+
+    pub fn build(self) -> Form {
+        self.form
+    }
+}
+
+/// A list of options, rows or columns, accepted from any array or vector of
+/// strings.
+pub struct OptionList(Vec<String>);
+
+impl<S: Into<String>, const N: usize> From<[S; N]> for OptionList {
+    fn from(options: [S; N]) -> Self {
+        Self(options.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<S: Into<String>> From<Vec<S>> for OptionList {
+    fn from(options: Vec<S>) -> Self {
+        Self(options.into_iter().map(Into::into).collect())
+    }
+}
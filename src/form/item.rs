@@ -0,0 +1,49 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::block::Block;
+use crate::locale::Locale;
+use crate::question::{Question, QuestionAction};
+
+/// An entry in a form: a question to answer, or a block of content to read.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FormItem {
+    Question(Question),
+    Block(Block),
+}
+
+impl FormItem {
+    pub fn question(&self) -> Option<&Question> {
+        match self {
+            Self::Question(question) => Some(question),
+            Self::Block(_) => None,
+        }
+    }
+
+    pub fn question_mut(&mut self) -> Option<&mut Question> {
+        match self {
+            Self::Question(question) => Some(question),
+            Self::Block(_) => None,
+        }
+    }
+
+    pub fn edit(
+        &mut self,
+        ui: &mut egui::Ui,
+        is_first: bool,
+        is_last: bool,
+        locale: &Locale,
+    ) -> Option<QuestionAction> {
+        match self {
+            Self::Question(question) => question.edit(ui, is_first, is_last, locale),
+            Self::Block(block) => block.edit(ui, is_first, is_last),
+        }
+    }
+
+    pub fn preview(&mut self, ui: &mut egui::Ui, locale: &Locale) {
+        match self {
+            Self::Question(question) => question.preview(ui, locale),
+            Self::Block(block) => block.show(ui),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+/// Seconds a state has to stay unchanged before it becomes its own undo step,
+/// so that typing a word is undone as a whole rather than letter by letter.
+const STABLE_TIME: f64 = 1.0;
+const MAX_UNDOS: usize = 100;
+
+/// Undo and redo stacks of snapshots, fed with the current state whenever it
+/// may have changed.
+#[derive(Clone, Debug)]
+pub struct History<State> {
+    undos: Vec<State>,
+    redos: Vec<State>,
+    current: Option<State>,
+    pending: Option<(f64, State)>,
+}
+
+impl<State> Default for History<State> {
+    fn default() -> Self {
+        Self {
+            undos: vec![],
+            redos: vec![],
+            current: None,
+            pending: None,
+        }
+    }
+}
+
+impl<State: Clone + PartialEq> History<State> {
+    /// Returns whether `state` differs from the state fed in last time.
+    pub fn feed(&mut self, time: f64, state: State) -> bool {
+        let Some(current) = &self.current else {
+            self.current = Some(state);
+            return false;
+        };
+        if *current == state {
+            return self.pending.take().is_some();
+        }
+        match &self.pending {
+            Some((since, pending)) if *pending == state => {
+                if time - since >= STABLE_TIME {
+                    self.commit(state);
+                }
+                false
+            }
+            _ => {
+                // A state that stayed unchanged long enough is its own step,
+                // even if nothing was fed while it waited.
+                if let Some((since, pending)) = self.pending.take() {
+                    if time - since >= STABLE_TIME {
+                        self.commit(pending);
+                    }
+                }
+                self.pending = Some((time, state));
+                true
+            }
+        }
+    }
+
+    fn commit(&mut self, state: State) {
+        if let Some(current) = self.current.replace(state) {
+            self.undos.push(current);
+            if self.undos.len() > MAX_UNDOS {
+                self.undos.remove(0);
+            }
+        }
+        self.redos.clear();
+        self.pending = None;
+    }
+
+    /// Whether there is anything to undo, as of the last state fed in.
+    pub fn can_undo(&self) -> bool {
+        !self.undos.is_empty() || self.pending.is_some()
+    }
+
+    /// Whether there is anything to redo, as of the last state fed in.
+    pub fn can_redo(&self) -> bool {
+        !self.redos.is_empty() && self.pending.is_none()
+    }
+
+    /// Returns the state before the latest edit, committing `state` first if it
+    /// has not been recorded yet.
+    pub fn undo(&mut self, state: State) -> Option<State> {
+        if self.current.as_ref() != Some(&state) {
+            self.commit(state);
+        }
+        self.pending = None;
+        let previous = self.undos.pop()?;
+        if let Some(current) = self.current.replace(previous.clone()) {
+            self.redos.push(current);
+        }
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, state: State) -> Option<State> {
+        if self.current.as_ref() != Some(&state) {
+            self.commit(state);
+            return None;
+        }
+        self.pending = None;
+        let next = self.redos.pop()?;
+        if let Some(current) = self.current.replace(next.clone()) {
+            self.undos.push(current);
+        }
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_become_steps_once_stable() {
+        let mut history = History::default();
+        assert!(!history.feed(0.0, "a"));
+        assert!(history.feed(0.1, "ab"));
+        assert!(history.feed(0.2, "abc"));
+        assert!(history.can_undo());
+        // Nothing is fed while the state stays the same, so the next change
+        // commits it as its own step.
+        assert!(history.feed(5.0, "abcd"));
+
+        assert_eq!(history.undo("abcd"), Some("abc"));
+        assert_eq!(history.undo("abc"), Some("a"));
+        assert_eq!(history.undo("a"), None);
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+        assert_eq!(history.redo("a"), Some("abc"));
+        assert_eq!(history.redo("abc"), Some("abcd"));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn editing_clears_redos() {
+        let mut history = History::default();
+        history.feed(0.0, 1);
+        history.feed(0.0, 2);
+        assert_eq!(history.undo(2), Some(1));
+        assert!(history.feed(1.0, 3));
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(3), None);
+        assert_eq!(history.undo(3), Some(1));
+    }
+
+    #[test]
+    fn changing_back_is_not_an_edit() {
+        let mut history = History::default();
+        history.feed(0.0, 1);
+        assert!(history.feed(0.1, 2));
+        assert!(history.feed(0.2, 1));
+        assert!(!history.can_undo());
+    }
+}
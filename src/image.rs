@@ -0,0 +1,177 @@
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// A picture embedded in a form, kept as the bytes of its file so that the
+/// form stays self-contained.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Image {
+    pub name: String,
+    /// Shared between clones, so that copies of the form, like those kept for
+    /// undo, don't copy the bytes.
+    #[serde(with = "base64_data")]
+    pub data: Arc<[u8]>,
+    /// Hash of `data`, identifying the decoded texture.
+    #[serde(skip)]
+    key: OnceLock<u64>,
+}
+
+impl PartialEq for Image {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && (Arc::ptr_eq(&self.data, &other.data) || self.data == other.data)
+    }
+}
+
+impl Image {
+    pub fn new(name: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            data: data.into(),
+            key: OnceLock::new(),
+        }
+    }
+
+    /// Reads the image file at `path`, checking that it can be shown.
+    pub fn open(path: &std::path::Path) -> Result<Self, String> {
+        let data = std::fs::read(path).map_err(|error| error.to_string())?;
+        image::load_from_memory(&data).map_err(|error| error.to_string())?;
+        let name = path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+        Ok(Self::new(name, data))
+    }
+
+    /// Shows the image scaled down to fit `max_width`.
+    pub fn show(&self, ui: &mut egui::Ui, max_width: f32) -> Option<egui::Response> {
+        let Some(texture) = self.texture(ui.ctx()) else {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!("Could not show {}", self.name),
+            );
+            return None;
+        };
+        let mut size = texture.size_vec2();
+        if size.x > max_width {
+            size *= max_width / size.x;
+        }
+        Some(ui.image(texture.id(), size))
+    }
+
+    /// The decoded image, decoding it the first time it's needed.
+    fn texture(&self, ctx: &egui::Context) -> Option<egui::TextureHandle> {
+        let key = *self.key.get_or_init(|| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            self.data.hash(&mut hasher);
+            hasher.finish()
+        });
+        let id = egui::Id::new(("image", key));
+        if let Some(texture) = ctx.memory_mut(|memory| memory.data.get_temp(id)) {
+            return texture;
+        }
+        let texture = image::load_from_memory(&self.data).ok().map(|decoded| {
+            let decoded = decoded.to_rgba8();
+            let size = [decoded.width() as usize, decoded.height() as usize];
+            let pixels = egui::ColorImage::from_rgba_unmultiplied(size, decoded.as_raw());
+            ctx.load_texture(&self.name, pixels, Default::default())
+        });
+        ctx.memory_mut(|memory| memory.data.insert_temp(id, texture.clone()));
+        texture
+    }
+}
+
+/// Shows `image` with a button to remove it, or a button to add one from an
+/// image file.
+pub fn edit(ui: &mut egui::Ui, image: &mut Option<Image>, max_width: f32) {
+    let id = ui.next_auto_id();
+    if let Some(current) = image {
+        current.show(ui, max_width);
+        let remove = ui
+            .horizontal(|ui| {
+                ui.label(&current.name);
+                ui.button("Remove image").clicked()
+            })
+            .inner;
+        if remove {
+            *image = None;
+        }
+        return;
+    }
+
+    // The path being entered and the error from the last attempt, while the
+    // path field is open.
+    let Some((mut path, mut error)) = ui.data_mut(|data| data.get_temp::<(String, String)>(id))
+    else {
+        if ui.small_button("🖼 Add image").clicked() {
+            ui.data_mut(|data| data.insert_temp(id, (String::new(), String::new())));
+        }
+        return;
+    };
+    let mut close = false;
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut path).hint_text("Image file"));
+        if ui.button("Add").clicked() {
+            match Image::open(std::path::Path::new(path.trim())) {
+                Ok(opened) => {
+                    *image = Some(opened);
+                    close = true;
+                }
+                Err(message) => error = format!("Could not open image: {}", message),
+            }
+        }
+        if ui.button("Cancel").clicked() {
+            close = true;
+        }
+    });
+    if close {
+        ui.data_mut(|data| data.remove::<(String, String)>(id));
+        return;
+    }
+    if !error.is_empty() {
+        ui.colored_label(ui.visuals().error_fg_color, &error);
+    }
+    ui.data_mut(|data| data.insert_temp(id, (path, error)));
+}
+
+/// Saves file data as base64 text rather than a long list of numbers.
+pub(crate) mod base64_data {
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<Vec<u8>>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .map(T::from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_share_data() {
+        let image = Image::new("dot.png", vec![1, 2, 3]);
+        let copy = image.clone();
+        assert!(Arc::ptr_eq(&image.data, &copy.data));
+        assert_eq!(image, copy);
+        assert_ne!(image, Image::new("dot.png", vec![1, 2]));
+    }
+
+    #[test]
+    fn saves_data_as_base64() {
+        let image = Image::new("dot.png", vec![1, 2, 3]);
+        let json = serde_json::to_string(&image).unwrap();
+        assert_eq!(json, r#"{"name":"dot.png","data":"AQID"}"#);
+        assert_eq!(serde_json::from_str::<Image>(&json).unwrap(), image);
+    }
+}
@@ -1,5 +1,29 @@
 mod app;
+mod block;
+mod calendar;
 mod form;
-mod question;
+mod history;
+mod image;
+mod locale;
+mod markdown;
+mod pdf;
+pub mod question;
+mod report;
+mod response;
+mod template;
+mod time;
+mod widgets;
+mod workspace;
 
 pub use app::EformApp;
+pub use block::Block;
+pub use eframe::egui;
+pub use form::{Form, FormBuilder, FormItem, OptionList};
+pub use image::Image;
+pub use locale::{DateOrder, Locale};
+pub use question::{
+    register_question_type, CustomQuestionType, Question, QuestionConfig, QuestionKind,
+    QuestionType, QuestionValue,
+};
+pub use response::Response;
+pub use widgets::{FormEditor, FormFiller};
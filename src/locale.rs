@@ -0,0 +1,83 @@
+//! How dates and times are written in a form, its responses and its reports.
+
+use serde::{Deserialize, Serialize};
+
+use crate::question::value_types::{DateValue, TimeValue};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Locale {
+    pub date_order: DateOrder,
+    /// Whether times are written on a 24-hour clock instead of with AM/PM.
+    pub twenty_four_hour: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum DateOrder {
+    /// MM/DD/YYYY
+    #[default]
+    MonthDayYear,
+    /// DD/MM/YYYY
+    DayMonthYear,
+    /// YYYY-MM-DD
+    YearMonthDay,
+}
+
+impl DateOrder {
+    pub const ALL: [Self; 3] = [Self::MonthDayYear, Self::DayMonthYear, Self::YearMonthDay];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MonthDayYear => "MM/DD/YYYY",
+            Self::DayMonthYear => "DD/MM/YYYY",
+            Self::YearMonthDay => "YYYY-MM-DD",
+        }
+    }
+}
+
+impl Locale {
+    /// Common combinations of settings, by name.
+    pub fn presets() -> [(&'static str, Self); 3] {
+        [
+            (
+                "United States",
+                Self {
+                    date_order: DateOrder::MonthDayYear,
+                    twenty_four_hour: false,
+                },
+            ),
+            (
+                "Europe",
+                Self {
+                    date_order: DateOrder::DayMonthYear,
+                    twenty_four_hour: true,
+                },
+            ),
+            (
+                "ISO 8601",
+                Self {
+                    date_order: DateOrder::YearMonthDay,
+                    twenty_four_hour: true,
+                },
+            ),
+        ]
+    }
+
+    /// `date` in this locale's order, leaving out the year if `include_year`
+    /// is false.
+    pub fn format_date(&self, date: &DateValue, include_year: bool) -> String {
+        let (year, month, day) = (date.year, date.month, date.day);
+        match (self.date_order, include_year) {
+            (DateOrder::MonthDayYear, true) => format!("{:02}/{:02}/{:04}", month, day, year),
+            (DateOrder::MonthDayYear, false) => format!("{:02}/{:02}", month, day),
+            (DateOrder::DayMonthYear, true) => format!("{:02}/{:02}/{:04}", day, month, year),
+            (DateOrder::DayMonthYear, false) => format!("{:02}/{:02}", day, month),
+            (DateOrder::YearMonthDay, true) => format!("{:04}-{:02}-{:02}", year, month, day),
+            (DateOrder::YearMonthDay, false) => format!("{:02}-{:02}", month, day),
+        }
+    }
+
+    /// `time` as a time of day on this locale's clock.
+    pub fn format_time(&self, time: &TimeValue, seconds: bool) -> String {
+        time.format(self.twenty_four_hour, seconds)
+    }
+}
@@ -0,0 +1,251 @@
+//! Shows the basic Markdown allowed in descriptions: paragraphs, bold,
+//! italics, inline code, links and lists.
+
+use eframe::egui;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+pub fn show(ui: &mut egui::Ui, text: &str) {
+    for line in lines(text) {
+        line.show(ui);
+    }
+}
+
+/// Splits `text` into the lines to show.
+fn lines(text: &str) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut line = Line::default();
+    let mut lists: Vec<Option<u64>> = vec![];
+    let mut strong = 0;
+    let mut emphasis = 0;
+    let mut link: Option<(String, String)> = None;
+    for event in Parser::new(text) {
+        match event {
+            Event::Start(Tag::List(start)) => {
+                line.finish(&mut lines);
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                line.finish(&mut lines);
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                line.finish(&mut lines);
+                line.indent = lists.len();
+                line.marker = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        Some(format!("{}. ", *number - 1))
+                    }
+                    _ => Some("• ".into()),
+                };
+            }
+            Event::Start(Tag::Strong | Tag::Heading { .. }) => strong += 1,
+            Event::End(TagEnd::Strong) => strong -= 1,
+            Event::End(TagEnd::Heading(_)) => {
+                strong -= 1;
+                line.finish(&mut lines);
+            }
+            Event::Start(Tag::Emphasis) => emphasis += 1,
+            Event::End(TagEnd::Emphasis) => emphasis -= 1,
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = Some((String::new(), dest_url.into_string()));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((text, url)) = link.take() {
+                    line.spans.push(Span::Link(text, url));
+                }
+            }
+            Event::Text(text) | Event::Code(text) if link.is_some() => {
+                if let Some((link_text, _)) = &mut link {
+                    link_text.push_str(&text);
+                }
+            }
+            Event::Text(text) => {
+                let style = Style {
+                    strong: strong > 0,
+                    italics: emphasis > 0,
+                    code: false,
+                };
+                line.spans.push(Span::Text(text.into_string(), style));
+            }
+            Event::Code(text) => {
+                let style = Style {
+                    code: true,
+                    ..Style::default()
+                };
+                line.spans.push(Span::Text(text.into_string(), style));
+            }
+            Event::SoftBreak => line.spans.push(Span::Text(" ".into(), Style::default())),
+            Event::HardBreak | Event::End(TagEnd::Paragraph | TagEnd::Item) => {
+                line.finish(&mut lines)
+            }
+            _ => {}
+        }
+    }
+    line.finish(&mut lines);
+    lines
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Style {
+    strong: bool,
+    italics: bool,
+    code: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Span {
+    Text(String, Style),
+    /// Link text and URL.
+    Link(String, String),
+}
+
+/// The text gathered for one line of output.
+#[derive(Debug, Default, PartialEq)]
+struct Line {
+    spans: Vec<Span>,
+    /// Bullet or number of the list item the line starts.
+    marker: Option<String>,
+    /// How deeply the line's list is nested.
+    indent: usize,
+}
+
+impl Line {
+    /// Moves the gathered text into `lines`, if there is any, keeping the indent
+    /// for the lines that follow.
+    fn finish(&mut self, lines: &mut Vec<Line>) {
+        if self.spans.is_empty() && self.marker.is_none() {
+            return;
+        }
+        lines.push(Line {
+            spans: std::mem::take(&mut self.spans),
+            marker: self.marker.take(),
+            indent: self.indent,
+        });
+    }
+
+    fn show(self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            ui.add_space(self.indent.saturating_sub(1) as f32 * 16.0);
+            if let Some(marker) = self.marker {
+                ui.label(marker);
+            }
+            for span in self.spans {
+                match span {
+                    Span::Text(text, style) => {
+                        let mut text = egui::RichText::new(text);
+                        if style.strong {
+                            text = text.strong();
+                        }
+                        if style.italics {
+                            text = text.italics();
+                        }
+                        if style.code {
+                            text = text.code();
+                        }
+                        ui.label(text)
+                    }
+                    Span::Link(text, url) => ui.hyperlink_to(text, url),
+                };
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Span {
+        Span::Text(text.into(), Style::default())
+    }
+
+    #[test]
+    fn paragraphs_and_headings() {
+        let lines = lines("# Title\n\nFirst line\nsame paragraph\n\nSecond");
+        let title = Style {
+            strong: true,
+            ..Style::default()
+        };
+        assert_eq!(
+            lines,
+            [
+                Line {
+                    spans: vec![Span::Text("Title".into(), title)],
+                    ..Line::default()
+                },
+                Line {
+                    spans: vec![text("First line"), text(" "), text("same paragraph")],
+                    ..Line::default()
+                },
+                Line {
+                    spans: vec![text("Second")],
+                    ..Line::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lists() {
+        let lines = lines("3. one\n4. two\n   - three\n   - four");
+        let items: Vec<_> = lines
+            .iter()
+            .map(|line| (line.marker.as_deref(), line.indent))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                (Some("3. "), 1),
+                (Some("4. "), 1),
+                (Some("• "), 2),
+                (Some("• "), 2)
+            ]
+        );
+        assert_eq!(lines[3].spans, [text("four")]);
+    }
+
+    #[test]
+    fn inline_styles_and_links() {
+        let lines = lines("**bold *both*** _it_ `code` [site](https://example.com)");
+        assert_eq!(
+            lines[0].spans,
+            [
+                Span::Text(
+                    "bold ".into(),
+                    Style {
+                        strong: true,
+                        ..Style::default()
+                    }
+                ),
+                Span::Text(
+                    "both".into(),
+                    Style {
+                        strong: true,
+                        italics: true,
+                        code: false
+                    }
+                ),
+                text(" "),
+                Span::Text(
+                    "it".into(),
+                    Style {
+                        italics: true,
+                        ..Style::default()
+                    }
+                ),
+                text(" "),
+                Span::Text(
+                    "code".into(),
+                    Style {
+                        code: true,
+                        ..Style::default()
+                    }
+                ),
+                text(" "),
+                Span::Link("site".into(), "https://example.com".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use std::fmt::Write;
+
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 56.0;
+
+/// Advance widths of the standard Helvetica font for the printable ASCII
+/// characters, in thousandths of the font size.
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '..'/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // '0'..'?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // '@'..'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // 'P'..'_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // '`'..'o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p'..'~'
+];
+
+#[derive(Clone, Copy)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+        }
+    }
+
+    fn text_width(self, text: &str, size: f32) -> f32 {
+        let width: u32 = text
+            .chars()
+            .map(|c| match c {
+                ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as u32,
+                _ => 556,
+            })
+            .sum();
+        let scale = match self {
+            Self::Regular => 1.0,
+            Self::Bold => 1.08,
+        };
+        width as f32 * size * scale / 1000.0
+    }
+}
+
+/// A minimal PDF writer that lays out text and bars top to bottom on Letter
+/// pages, using the standard Helvetica fonts so that nothing has to be embedded.
+pub struct Pdf {
+    pages: Vec<String>,
+    y: f32,
+}
+
+impl Pdf {
+    pub fn new() -> Self {
+        Self {
+            pages: vec![String::new()],
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    pub fn content_width(&self) -> f32 {
+        PAGE_WIDTH - MARGIN * 2.0
+    }
+
+    pub fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Writes `text` wrapped to the page width, starting at `indent` from the left margin.
+    pub fn text(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        let width = self.content_width() - indent;
+        for line in wrap(text, font, size, width) {
+            self.reserve(size * 1.3);
+            self.y -= size * 1.3;
+            self.draw_text(&line, font, size, MARGIN + indent, self.y + size * 0.3);
+        }
+    }
+
+    /// Writes a labelled horizontal bar filled to `fraction`, followed by `caption`.
+    pub fn bar(&mut self, label: &str, fraction: f32, caption: &str, indent: f32) {
+        let size = 10.0;
+        let label_width = self.content_width() * 0.4 - indent;
+        let bar_x = MARGIN + self.content_width() * 0.4;
+        let bar_width = self.content_width() * 0.4;
+        let lines = wrap(label, Font::Regular, size, label_width);
+        let height = lines.len() as f32 * size * 1.3 + 4.0;
+        self.reserve(height);
+        let top = self.y;
+        for line in lines {
+            self.y -= size * 1.3;
+            self.draw_text(
+                &line,
+                Font::Regular,
+                size,
+                MARGIN + indent,
+                self.y + size * 0.3,
+            );
+        }
+        let bar_y = top - size * 1.3 + 1.0;
+        let page = self.pages.last_mut().unwrap();
+        let _ = writeln!(
+            page,
+            "0.9 0.9 0.9 rg {} {} {} {} re f",
+            bar_x, bar_y, bar_width, size
+        );
+        let _ = writeln!(
+            page,
+            "0.26 0.52 0.96 rg {} {} {} {} re f 0 g",
+            bar_x,
+            bar_y,
+            bar_width * fraction.clamp(0.0, 1.0),
+            size
+        );
+        self.draw_text(
+            caption,
+            Font::Regular,
+            size,
+            bar_x + bar_width + 6.0,
+            top - size * 1.3 + size * 0.3,
+        );
+        self.y -= 4.0;
+    }
+
+    /// Starts a new page unless at least `height` is left on the current one.
+    pub fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.pages.push(String::new());
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn draw_text(&mut self, text: &str, font: Font, size: f32, x: f32, y: f32) {
+        let page = self.pages.last_mut().unwrap();
+        let _ = writeln!(
+            page,
+            "BT /{} {} Tf {} {} Td ({}) Tj ET",
+            font.resource(),
+            size,
+            x,
+            y,
+            encode(text)
+        );
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            String::new(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .into(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .into(),
+        ];
+        let mut kids = vec![];
+        for content in self.pages {
+            let page_id = objects.len() + 1;
+            kids.push(format!("{} 0 R", page_id));
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ));
+        }
+        objects[1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            kids.len()
+        );
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = vec![];
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            let _ = write!(out, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+        }
+        let xref = out.len();
+        let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(out, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            out,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        );
+        out.into_bytes()
+    }
+}
+
+/// Splits `text` into lines that fit within `width`, breaking on spaces where possible.
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if font.text_width(&candidate, size) <= width || line.is_empty() {
+                line = candidate;
+            } else {
+                lines.push(std::mem::take(&mut line));
+                line = word.to_string();
+            }
+            while font.text_width(&line, size) > width && line.chars().count() > 1 {
+                let mut split = line.len();
+                while font.text_width(&line[..split], size) > width {
+                    split = line[..split].char_indices().last().unwrap().0;
+                }
+                if split == 0 {
+                    break;
+                }
+                lines.push(line[..split].to_string());
+                line = line[split..].to_string();
+            }
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Encodes `text` as the body of a PDF string literal in WinAnsiEncoding.
+fn encode(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+                continue;
+            }
+            ' '..='~' => {
+                out.push(c);
+                continue;
+            }
+            '€' => 0x80,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            _ => b'?',
+        };
+        let _ = write!(out, "\\{:03o}", byte);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The byte offsets listed in the cross-reference table of `pdf`.
+    fn xref_offsets(pdf: &str) -> Vec<usize> {
+        let start: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|rest| rest.lines().next())
+            .and_then(|line| line.parse().ok())
+            .unwrap();
+        assert!(pdf[start..].starts_with("xref\n"));
+        pdf[start..]
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn cross_references_point_at_objects() {
+        let mut pdf = Pdf::new();
+        pdf.text("Title", Font::Bold, 20.0, 0.0);
+        pdf.bar("Yes", 0.5, "1 (50%)", 0.0);
+        let out = String::from_utf8(pdf.finish()).unwrap();
+        let offsets = xref_offsets(&out);
+        // Catalog, pages, two fonts, and a page with its contents.
+        assert_eq!(offsets.len(), 6);
+        for (i, offset) in offsets.iter().enumerate() {
+            assert!(out[*offset..].starts_with(&format!("{} 0 obj\n", i + 1)));
+        }
+        assert!(out.contains("/Size 7 /Root 1 0 R"));
+        assert!(out.ends_with("%%EOF\n"));
+    }
+
+    #[test]
+    fn long_text_breaks_onto_new_pages() {
+        let mut pdf = Pdf::new();
+        for i in 0..100 {
+            pdf.text(&format!("Line {}", i), Font::Regular, 12.0, 0.0);
+        }
+        let out = String::from_utf8(pdf.finish()).unwrap();
+        let pages = out.matches("/Type /Page /Parent").count();
+        assert!(pages > 1);
+        assert!(out.contains(&format!("/Count {} >>", pages)));
+        assert_eq!(xref_offsets(&out).len(), 4 + pages * 2);
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        assert_eq!(encode(r"a (b) \c"), r"a \(b\) \\c");
+        assert_eq!(encode("café – €"), r"caf\351 \226 \200");
+        assert_eq!(encode("日"), r"\077");
+    }
+
+    #[test]
+    fn wraps_to_width() {
+        let lines = wrap("one two three four", Font::Regular, 10.0, 40.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.join(" "), "one two three four");
+        for line in lines.iter() {
+            assert!(Font::Regular.text_width(line, 10.0) <= 40.0);
+        }
+        assert_eq!(wrap("", Font::Regular, 10.0, 40.0), [""]);
+    }
+}
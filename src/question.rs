@@ -1,84 +1,236 @@
 use eframe::egui;
 use serde::{Deserialize, Serialize};
 
+use crate::locale::Locale;
+
 mod config;
-mod config_types;
+pub mod config_types;
+mod kind;
+mod question_type;
+mod registry;
+mod summary;
 mod value;
-mod value_types;
+pub mod value_types;
 
-use config::QuestionConfig;
-use value::QuestionValue;
+pub use config::QuestionConfig;
+pub use kind::QuestionKind;
+pub use question_type::{CustomQuestionType, QuestionType};
+pub use registry::register_question_type;
+pub use summary::{QuestionSummary, Summary};
+pub use value::QuestionValue;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SavedQuestion", into = "SavedQuestion")]
 pub struct Question {
+    pub id: usize,
     pub name: String,
-    pub config: QuestionConfig,
-    pub value: QuestionValue,
+    /// Help shown under the name, in Markdown.
+    pub description: String,
+    /// Picture shown above the answer input.
+    pub image: Option<crate::image::Image>,
+    pub kind: QuestionKind,
+}
+
+/// How a question is stored, with the config and the answer kept apart so that
+/// saves stay readable and a mismatched pair can be repaired when loading.
+#[derive(Serialize, Deserialize)]
+struct SavedQuestion {
+    #[serde(default)]
+    id: usize,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    image: Option<crate::image::Image>,
+    config: QuestionConfig,
+    value: QuestionValue,
+}
+
+impl From<SavedQuestion> for Question {
+    fn from(saved: SavedQuestion) -> Self {
+        Self {
+            id: saved.id,
+            name: saved.name,
+            description: saved.description,
+            image: saved.image,
+            kind: QuestionKind::from_parts(saved.config, saved.value),
+        }
+    }
+}
+
+impl From<Question> for SavedQuestion {
+    fn from(question: Question) -> Self {
+        Self {
+            id: question.id,
+            name: question.name,
+            description: question.description,
+            image: question.image,
+            config: question.kind.config(),
+            value: question.kind.value(),
+        }
+    }
+}
+
+/// A change to the item list requested from a question's or block's editor.
+pub enum QuestionAction {
+    MoveUp,
+    MoveDown,
+    Duplicate,
+    InsertBelow,
+    Delete,
 }
 
 impl Question {
-    pub fn new() -> Self {
+    pub fn new(id: usize) -> Self {
         Self {
+            id,
             name: "Question".into(),
-            config: QuestionConfig::ShortAnswer(config_types::ShortAnswerConfig::default()),
-            value: QuestionValue::ShortAnswer(value_types::ShortAnswerValue::default()),
+            description: String::new(),
+            image: None,
+            kind: QuestionKind::new(QuestionConfig::ShortAnswer(config_types::ShortAnswerConfig)),
         }
     }
 
-    pub fn types_list() -> Vec<(QuestionConfig, QuestionValue)> {
-        QuestionConfig::types_list()
-            .iter()
-            .zip(QuestionValue::types_list())
-            .into_iter()
-            .map(|(config, value)| (config.clone(), value))
-            .collect()
+    pub fn reset_value(&mut self) {
+        self.kind.reset_value();
     }
 
-    pub fn reset_value(&mut self) {
-        match (&mut self.config, &mut self.value) {
-            (QuestionConfig::ShortAnswer(_), QuestionValue::ShortAnswer(value)) => {
-                value.text = String::new();
-            }
-            (QuestionConfig::Paragraph(_), QuestionValue::Paragraph(value)) => {
-                value.text = String::new();
-            }
-            (QuestionConfig::MultipleChoice(_), QuestionValue::MultipleChoice(value)) => {
-                value.choice = String::new();
-            }
-            (QuestionConfig::Checkboxes(config), QuestionValue::Checkboxes(value)) => {
-                value.choices = vec![false; config.options.len()];
+    pub fn validate(&self, locale: &Locale) -> Result<(), String> {
+        self.kind.validate(locale)
+    }
+
+    /// Switches to the type of `config`, carrying over options, rows and columns
+    /// and as much of the current answer as the new type can hold.
+    pub fn change_type(&mut self, mut config: QuestionConfig) {
+        let old_config = self.kind.config();
+        if let (Some(options), Some(new_options)) = (old_config.options(), config.options_mut()) {
+            new_options.clone_from(options);
+        }
+        if let (Some(images), Some(new_images)) =
+            (old_config.option_images(), config.option_images_mut())
+        {
+            new_images.clone_from(images);
+        }
+        if let (Some((rows, columns)), Some((new_rows, new_columns))) =
+            (old_config.grid(), config.grid_mut())
+        {
+            new_rows.clone_from(rows);
+            new_columns.clone_from(columns);
+        }
+        let text = match &self.kind {
+            QuestionKind::ShortAnswer(_, value) => Some(value.text.clone()),
+            QuestionKind::Paragraph(_, value) => Some(value.text.clone()),
+            _ => None,
+        };
+        let selected = self.selected_options();
+        let selected_grid = self.selected_grid();
+
+        self.kind = QuestionKind::new(config);
+
+        match &mut self.kind {
+            QuestionKind::ShortAnswer(_, value) => {
+                if let Some(text) = text {
+                    value.text = text.replace('\n', " ");
+                }
             }
-            (QuestionConfig::Dropdown(_), QuestionValue::Dropdown(value)) => {
-                value.choice = String::new();
+            QuestionKind::Paragraph(_, value) => {
+                if let Some(text) = text {
+                    value.text = text;
+                }
             }
-            (QuestionConfig::LinearScale(config), QuestionValue::LinearScale(value)) => {
-                value.value = config.start;
+            QuestionKind::MultipleChoice(config, value) => {
+                if let Some(selected) = selected {
+                    value.choice = first_selected(&selected, &config.options);
+                }
             }
-            (
-                QuestionConfig::MultipleChoiceGrid(config),
-                QuestionValue::MultipleChoiceGrid(value),
-            ) => {
-                value.choices = vec![String::new(); config.rows.len()];
+            QuestionKind::Dropdown(config, value) => {
+                if let Some(selected) = selected {
+                    value.choice = first_selected(&selected, &config.options);
+                }
             }
-            (QuestionConfig::CheckboxGrid(config), QuestionValue::CheckboxGrid(value)) => {
-                value.choices = vec![vec![false; config.columns.len()]; config.rows.len()];
+            QuestionKind::Checkboxes(_, value) => {
+                if let Some(selected) = selected {
+                    value.choices = selected;
+                }
             }
-            (QuestionConfig::Date(_), QuestionValue::Date(value)) => {
-                value.year = 0;
-                value.month = 1;
-                value.day = 1;
+            QuestionKind::MultipleChoiceGrid(config, value) => {
+                if let Some(selected_grid) = selected_grid {
+                    value.choices = selected_grid
+                        .iter()
+                        .map(|selected| first_selected(selected, &config.columns))
+                        .collect();
+                }
             }
-            (QuestionConfig::Time(_), QuestionValue::Time(value)) => {
-                value.hour = 1;
-                value.minute = 0;
-                value.period = value_types::DayPeriod::AM;
+            QuestionKind::CheckboxGrid(_, value) => {
+                if let Some(selected_grid) = selected_grid {
+                    value.choices = selected_grid;
+                }
             }
-            _ => panic!("Config type is not the same as value type"),
+            _ => {}
         }
     }
 
-    pub fn edit(&mut self, ui: &mut egui::Ui) -> bool {
-        let mut should_delete = false;
+    /// Which options are selected, for choice questions.
+    fn selected_options(&self) -> Option<Vec<bool>> {
+        match &self.kind {
+            QuestionKind::MultipleChoice(config, value) => Some(
+                config
+                    .options
+                    .iter()
+                    .map(|option| *option == value.choice)
+                    .collect(),
+            ),
+            QuestionKind::Dropdown(config, value) => Some(
+                config
+                    .options
+                    .iter()
+                    .map(|option| *option == value.choice)
+                    .collect(),
+            ),
+            QuestionKind::Checkboxes(config, value) => Some(
+                (0..config.options.len())
+                    .map(|i| value.choices.get(i) == Some(&true))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Which cells are selected, row by row, for grid questions.
+    fn selected_grid(&self) -> Option<Vec<Vec<bool>>> {
+        match &self.kind {
+            QuestionKind::MultipleChoiceGrid(config, value) => Some(
+                (0..config.rows.len())
+                    .map(|y| {
+                        config
+                            .columns
+                            .iter()
+                            .map(|column| value.choices.get(y) == Some(column))
+                            .collect()
+                    })
+                    .collect(),
+            ),
+            QuestionKind::CheckboxGrid(config, value) => Some(
+                (0..config.rows.len())
+                    .map(|y| {
+                        (0..config.columns.len())
+                            .map(|x| value.choices.get(y).and_then(|row| row.get(x)) == Some(&true))
+                            .collect()
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn edit(
+        &mut self,
+        ui: &mut egui::Ui,
+        is_first: bool,
+        is_last: bool,
+        locale: &Locale,
+    ) -> Option<QuestionAction> {
+        let mut action = None;
         ui.group(|ui| {
             ui.horizontal(|ui| {
                 ui.text_edit_singleline(&mut self.name);
@@ -86,154 +238,221 @@ impl Question {
                     eframe::epaint::vec2(132.0, 20.0),
                     egui::Layout::top_down_justified(eframe::emath::Align::Min),
                     |ui| {
-                        ui.menu_button(self.config.name(), |ui| {
+                        ui.menu_button(self.kind.name(), |ui| {
                             ui.set_width(120.0);
-                            for (config, value) in Self::types_list() {
+                            for config in QuestionConfig::types_list() {
                                 if ui.button(config.name()).clicked() {
-                                    self.config = config;
-                                    self.value = value;
+                                    self.change_type(config);
                                     ui.close_menu();
                                 }
                             }
                         });
                     },
                 );
-                if ui.button("❌").clicked() {
-                    should_delete = true;
-                }
+                action = item_buttons(ui, is_first, is_last);
             });
-            self.config.edit(ui);
+            ui.add(
+                egui::TextEdit::multiline(&mut self.description)
+                    .hint_text("Description (optional)")
+                    .desired_rows(1),
+            );
+            crate::image::edit(ui, &mut self.image, 300.0);
+            self.kind.edit(ui, locale);
         });
-        should_delete
+        action
     }
 
-    pub fn preview(&mut self, ui: &mut egui::Ui) {
+    /// Shows the question as a respondent sees it, with dates and times
+    /// written in `locale`.
+    pub fn preview(&mut self, ui: &mut egui::Ui, locale: &Locale) {
         ui.group(|ui| {
             ui.label(self.name.clone());
-            match (&mut self.config, &mut self.value) {
-                (QuestionConfig::ShortAnswer(_), QuestionValue::ShortAnswer(value)) => {
-                    ui.add(egui::TextEdit::singleline(&mut value.text).hint_text("Your answer"));
-                }
-                (QuestionConfig::Paragraph(_), QuestionValue::Paragraph(value)) => {
-                    ui.add(egui::TextEdit::multiline(&mut value.text).hint_text("Your answer"));
-                }
-                (QuestionConfig::MultipleChoice(config), QuestionValue::MultipleChoice(value)) => {
-                    for option in config.options.iter() {
-                        ui.radio_value(&mut value.choice, option.clone(), option);
-                    }
-                }
-                (QuestionConfig::Checkboxes(config), QuestionValue::Checkboxes(value)) => {
-                    for (i, option) in config.options.iter().enumerate() {
-                        ui.checkbox(&mut value.choices[i], option);
-                    }
-                }
-                (QuestionConfig::Dropdown(config), QuestionValue::Dropdown(value)) => {
-                    let label = if value.choice.is_empty() {
-                        "Choose".into()
-                    } else {
-                        value.choice.clone()
-                    };
-                    ui.menu_button(label, |ui| {
-                        for option in config.options.iter() {
-                            if ui
-                                .selectable_value(&mut value.choice, option.clone(), option)
-                                .clicked()
-                            {
-                                ui.close_menu();
-                            }
-                        }
-                    });
-                }
-                (QuestionConfig::LinearScale(config), QuestionValue::LinearScale(value)) => {
-                    ui.horizontal(|ui| {
-                        for i in config.start..=config.end {
-                            ui.radio_value(&mut value.value, i, i.to_string());
-                        }
-                    });
-                }
-                (
-                    QuestionConfig::MultipleChoiceGrid(config),
-                    QuestionValue::MultipleChoiceGrid(value),
-                ) => {
-                    egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
-                        ui.label("");
-                        for column in config.columns.iter() {
-                            ui.label(column);
-                        }
-                        ui.end_row();
-                        for (y, row) in config.rows.iter().enumerate() {
-                            ui.label(row);
-                            for column in config.columns.iter() {
-                                ui.radio_value(&mut value.choices[y], column.clone(), "");
-                            }
-                            ui.end_row();
-                        }
-                    });
-                }
-                (QuestionConfig::CheckboxGrid(config), QuestionValue::CheckboxGrid(value)) => {
-                    egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
-                        ui.label("");
-                        for column in config.columns.iter() {
-                            ui.label(column);
-                        }
-                        ui.end_row();
-                        for (y, row) in config.rows.iter().enumerate() {
-                            ui.label(row);
-                            for x in 0..config.columns.len() {
-                                ui.checkbox(&mut value.choices[y][x], "");
-                            }
-                            ui.end_row();
-                        }
-                    });
-                }
-                (QuestionConfig::Date(_), QuestionValue::Date(value)) => {
-                    ui.label("MM  DD  YYYY");
-                    ui.horizontal(|ui| {
-                        ui.add(egui::DragValue::new(&mut value.month).clamp_range(1..=12));
-                        ui.label("/");
-                        ui.add(egui::DragValue::new(&mut value.day).clamp_range(1..=31));
-                        ui.label("/");
-                        ui.add(egui::DragValue::new(&mut value.year).clamp_range(0..=9999));
-                    });
-                }
-                (QuestionConfig::Time(_), QuestionValue::Time(value)) => {
-                    ui.label("Time");
-                    ui.horizontal(|ui| {
-                        ui.add(egui::DragValue::new(&mut value.hour).clamp_range(1..=12));
-                        ui.label(":");
-                        ui.add(egui::DragValue::new(&mut value.minute).clamp_range(0..=59));
-                        ui.menu_button(
-                            match value.period {
-                                value_types::DayPeriod::AM => "AM",
-                                value_types::DayPeriod::PM => "PM",
-                            },
-                            |ui| {
-                                if ui
-                                    .selectable_value(
-                                        &mut value.period,
-                                        value_types::DayPeriod::AM,
-                                        "AM",
-                                    )
-                                    .clicked()
-                                {
-                                    ui.close_menu();
-                                }
-                                if ui
-                                    .selectable_value(
-                                        &mut value.period,
-                                        value_types::DayPeriod::PM,
-                                        "PM",
-                                    )
-                                    .clicked()
-                                {
-                                    ui.close_menu();
-                                }
-                            },
-                        );
-                    });
-                }
-                _ => panic!("Config type is not the same as value type"),
+            if !self.description.is_empty() {
+                crate::markdown::show(ui, &self.description);
+            }
+            if let Some(image) = &self.image {
+                image.show(ui, ui.available_width());
             }
+            self.kind.preview(ui, locale);
         });
     }
 }
+
+/// The buttons for moving, duplicating, inserting below and deleting an item
+/// in the editor, returning the action of the one clicked.
+pub(crate) fn item_buttons(
+    ui: &mut egui::Ui,
+    is_first: bool,
+    is_last: bool,
+) -> Option<QuestionAction> {
+    let mut action = None;
+    if ui.add_enabled(!is_first, egui::Button::new("⏶")).clicked() {
+        action = Some(QuestionAction::MoveUp);
+    }
+    if ui.add_enabled(!is_last, egui::Button::new("⏷")).clicked() {
+        action = Some(QuestionAction::MoveDown);
+    }
+    if ui.button("🗐").on_hover_text("Duplicate").clicked() {
+        action = Some(QuestionAction::Duplicate);
+    }
+    if ui
+        .button("➕")
+        .on_hover_text("Insert question below")
+        .clicked()
+    {
+        action = Some(QuestionAction::InsertBelow);
+    }
+    if ui.button("❌").clicked() {
+        action = Some(QuestionAction::Delete);
+    }
+    action
+}
+
+/// The first option marked in `selected`, or an empty string if there is none.
+fn first_selected(selected: &[bool], options: &[String]) -> String {
+    selected
+        .iter()
+        .zip(options)
+        .find(|(selected, _)| **selected)
+        .map(|(_, option)| option.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config_types::*;
+    use value_types::*;
+
+    #[test]
+    fn loads_questions_saved_before_ids_and_descriptions() {
+        let json = r#"{
+            "name": "Name",
+            "config": { "ShortAnswer": null },
+            "value": { "ShortAnswer": { "text": "Ada" } }
+        }"#;
+        let question: Question = serde_json::from_str(json).unwrap();
+        assert_eq!(question.id, 0);
+        assert_eq!(question.name, "Name");
+        assert!(question.description.is_empty());
+        assert_eq!(
+            question.kind,
+            QuestionKind::ShortAnswer(ShortAnswerConfig, ShortAnswerValue { text: "Ada".into() })
+        );
+    }
+
+    #[test]
+    fn repairs_mismatched_answer_when_loading() {
+        let json = r#"{
+            "id": 3,
+            "name": "Rating",
+            "config": { "LinearScale": { "start": 1, "start_label": "", "end": 5, "end_label": "" } },
+            "value": { "ShortAnswer": { "text": "4" } }
+        }"#;
+        let question: Question = serde_json::from_str(json).unwrap();
+        assert_eq!(question.id, 3);
+        let QuestionKind::LinearScale(config, value) = question.kind else {
+            panic!("not a linear scale");
+        };
+        assert_eq!((config.start, config.end), (1, 5));
+        assert_eq!(value, LinearScaleValue { value: 1 });
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut question = Question::new(7);
+        question.description = "Help".into();
+        question.change_type(QuestionConfig::Paragraph(ParagraphConfig));
+        let json = serde_json::to_string(&question).unwrap();
+        assert_eq!(serde_json::from_str::<Question>(&json).unwrap(), question);
+    }
+
+    fn checkboxes(options: &[&str], choices: &[bool]) -> Question {
+        Question {
+            id: 0,
+            name: "Question".into(),
+            description: String::new(),
+            image: None,
+            kind: QuestionKind::Checkboxes(
+                CheckboxesConfig {
+                    options: options.iter().map(|option| option.to_string()).collect(),
+                    option_images: vec![],
+                },
+                CheckboxesValue {
+                    choices: choices.to_vec(),
+                },
+            ),
+        }
+    }
+
+    #[test]
+    fn options_and_choices_carry_over() {
+        let mut question = checkboxes(&["a", "b", "c"], &[false, true, true]);
+        question.change_type(QuestionConfig::MultipleChoice(
+            MultipleChoiceConfig::default(),
+        ));
+        let QuestionKind::MultipleChoice(config, value) = &question.kind else {
+            panic!("type did not change");
+        };
+        assert_eq!(config.options, ["a", "b", "c"]);
+        assert_eq!(value.choice, "b");
+
+        question.change_type(QuestionConfig::Checkboxes(CheckboxesConfig::default()));
+        let QuestionKind::Checkboxes(config, value) = &question.kind else {
+            panic!("type did not change");
+        };
+        assert_eq!(config.options, ["a", "b", "c"]);
+        assert_eq!(value.choices, [false, true, false]);
+    }
+
+    #[test]
+    fn grids_carry_over() {
+        let mut question = Question {
+            id: 0,
+            name: "Question".into(),
+            description: String::new(),
+            image: None,
+            kind: QuestionKind::CheckboxGrid(
+                CheckboxGridConfig {
+                    rows: vec!["r1".into(), "r2".into()],
+                    columns: vec!["c1".into(), "c2".into()],
+                },
+                CheckboxGridValue {
+                    choices: vec![vec![false, true], vec![true, true]],
+                },
+            ),
+        };
+        question.change_type(QuestionConfig::MultipleChoiceGrid(
+            MultipleChoiceGridConfig::default(),
+        ));
+        let QuestionKind::MultipleChoiceGrid(config, value) = &question.kind else {
+            panic!("type did not change");
+        };
+        assert_eq!(config.rows, ["r1", "r2"]);
+        assert_eq!(config.columns, ["c1", "c2"]);
+        assert_eq!(value.choices, ["c2", "c1"]);
+    }
+
+    #[test]
+    fn text_carries_over() {
+        let mut question = Question::new(0);
+        question.kind = QuestionKind::Paragraph(
+            ParagraphConfig,
+            ParagraphValue {
+                text: "two\nlines".into(),
+            },
+        );
+        question.change_type(QuestionConfig::ShortAnswer(ShortAnswerConfig));
+        let QuestionKind::ShortAnswer(_, value) = &question.kind else {
+            panic!("type did not change");
+        };
+        assert_eq!(value.text, "two lines");
+
+        // Answers that the new type can't hold are dropped.
+        question.change_type(QuestionConfig::Dropdown(DropdownConfig::default()));
+        let QuestionKind::Dropdown(_, value) = &question.kind else {
+            panic!("type did not change");
+        };
+        assert_eq!(value.choice, "");
+    }
+}
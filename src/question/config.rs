@@ -1,9 +1,10 @@
-use eframe::egui;
 use serde::{Deserialize, Serialize};
 
 use super::config_types;
+use super::question_type::QuestionType;
+use super::registry::{self, CustomData};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum QuestionConfig {
     ShortAnswer(config_types::ShortAnswerConfig),
     Paragraph(config_types::ParagraphConfig),
@@ -15,28 +16,42 @@ pub enum QuestionConfig {
     CheckboxGrid(config_types::CheckboxGridConfig),
     Date(config_types::DateConfig),
     Time(config_types::TimeConfig),
+    FileUpload(config_types::FileUploadConfig),
+    Number(config_types::NumberConfig),
+    Email(config_types::EmailConfig),
+    Phone(config_types::PhoneConfig),
+    Url(config_types::UrlConfig),
+    Address(config_types::AddressConfig),
+    Custom(CustomData),
 }
 
 impl QuestionConfig {
-    pub fn name(&self) -> String {
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::ShortAnswer(_) => "Short answer".into(),
-            Self::Paragraph(_) => "Paragraph".into(),
-            Self::MultipleChoice(_) => "Multiple choice".into(),
-            Self::Checkboxes(_) => "Checkboxes".into(),
-            Self::Dropdown(_) => "Dropdown".into(),
-            Self::LinearScale(_) => "Linear scale".into(),
-            Self::MultipleChoiceGrid(_) => "Multiple choice grid".into(),
-            Self::CheckboxGrid(_) => "Checkbox grid".into(),
-            Self::Date(_) => "Date".into(),
-            Self::Time(_) => "Time".into(),
+            Self::ShortAnswer(_) => config_types::ShortAnswerConfig::NAME,
+            Self::Paragraph(_) => config_types::ParagraphConfig::NAME,
+            Self::MultipleChoice(_) => config_types::MultipleChoiceConfig::NAME,
+            Self::Checkboxes(_) => config_types::CheckboxesConfig::NAME,
+            Self::Dropdown(_) => config_types::DropdownConfig::NAME,
+            Self::LinearScale(_) => config_types::LinearScaleConfig::NAME,
+            Self::MultipleChoiceGrid(_) => config_types::MultipleChoiceGridConfig::NAME,
+            Self::CheckboxGrid(_) => config_types::CheckboxGridConfig::NAME,
+            Self::Date(_) => config_types::DateConfig::NAME,
+            Self::Time(_) => config_types::TimeConfig::NAME,
+            Self::FileUpload(_) => config_types::FileUploadConfig::NAME,
+            Self::Number(_) => config_types::NumberConfig::NAME,
+            Self::Email(_) => config_types::EmailConfig::NAME,
+            Self::Phone(_) => config_types::PhoneConfig::NAME,
+            Self::Url(_) => config_types::UrlConfig::NAME,
+            Self::Address(_) => config_types::AddressConfig::NAME,
+            Self::Custom(data) => registry::find(&data.tag).map_or("Unknown type", |t| t.name()),
         }
     }
 
     pub fn types_list() -> Vec<Self> {
-        vec![
-            Self::ShortAnswer(config_types::ShortAnswerConfig::default()),
-            Self::Paragraph(config_types::ParagraphConfig::default()),
+        let mut types = vec![
+            Self::ShortAnswer(config_types::ShortAnswerConfig),
+            Self::Paragraph(config_types::ParagraphConfig),
             Self::MultipleChoice(config_types::MultipleChoiceConfig::default()),
             Self::Checkboxes(config_types::CheckboxesConfig::default()),
             Self::Dropdown(config_types::DropdownConfig::default()),
@@ -45,88 +60,72 @@ impl QuestionConfig {
             Self::CheckboxGrid(config_types::CheckboxGridConfig::default()),
             Self::Date(config_types::DateConfig::default()),
             Self::Time(config_types::TimeConfig::default()),
-        ]
+            Self::FileUpload(config_types::FileUploadConfig::default()),
+            Self::Number(config_types::NumberConfig::default()),
+            Self::Email(config_types::EmailConfig),
+            Self::Phone(config_types::PhoneConfig::default()),
+            Self::Url(config_types::UrlConfig),
+            Self::Address(config_types::AddressConfig),
+        ];
+        types.extend(registry::registered_types().into_iter().map(|t| {
+            Self::Custom(CustomData {
+                tag: t.tag().into(),
+                data: t.default_config(),
+            })
+        }));
+        types
     }
 
-    pub fn edit(&mut self, ui: &mut egui::Ui) {
+    /// The options of a choice question, or `None` for other types.
+    pub fn options(&self) -> Option<&Vec<String>> {
         match self {
-            Self::ShortAnswer(_) => {}
-            Self::Paragraph(_) => {}
-            Self::MultipleChoice(data) => edit_options(ui, &mut data.options, "Option"),
-            Self::Checkboxes(data) => edit_options(ui, &mut data.options, "Option"),
-            Self::Dropdown(data) => edit_options(ui, &mut data.options, "Option"),
-            Self::LinearScale(data) => {
-                ui.horizontal(|ui| {
-                    ui.add(egui::DragValue::new(&mut data.start).clamp_range(0..=1));
-                    ui.label("to");
-                    ui.add(egui::DragValue::new(&mut data.end).clamp_range(2..=10));
-                });
-                ui.horizontal(|ui| {
-                    ui.label(format!("{}", data.start));
-                    ui.add(
-                        egui::TextEdit::singleline(&mut data.start_label)
-                            .hint_text("Label (optional)")
-                            .desired_width(100.0),
-                    );
-                });
-                ui.horizontal(|ui| {
-                    ui.label(format!("{}", data.end));
-                    ui.add(
-                        egui::TextEdit::singleline(&mut data.end_label)
-                            .hint_text("Label (optional)")
-                            .desired_width(100.0),
-                    );
-                });
-            }
-            Self::MultipleChoiceGrid(data) => edit_grid(ui, &mut data.rows, &mut data.columns),
-            Self::CheckboxGrid(data) => edit_grid(ui, &mut data.rows, &mut data.columns),
-            Self::Date(_) => {}
-            Self::Time(_) => {}
+            Self::MultipleChoice(data) => Some(&data.options),
+            Self::Checkboxes(data) => Some(&data.options),
+            Self::Dropdown(data) => Some(&data.options),
+            _ => None,
         }
     }
-}
 
-impl PartialEq for QuestionConfig {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::ShortAnswer(_), Self::ShortAnswer(_)) => true,
-            (Self::Paragraph(_), Self::Paragraph(_)) => true,
-            (Self::MultipleChoice(_), Self::MultipleChoice(_)) => true,
-            (Self::Checkboxes(_), Self::Checkboxes(_)) => true,
-            (Self::Dropdown(_), Self::Dropdown(_)) => true,
-            (Self::LinearScale(_), Self::LinearScale(_)) => true,
-            (Self::MultipleChoiceGrid(_), Self::MultipleChoiceGrid(_)) => true,
-            (Self::CheckboxGrid(_), Self::CheckboxGrid(_)) => true,
-            (Self::Date(_), Self::Date(_)) => true,
-            (Self::Time(_), Self::Time(_)) => true,
-            _ => false,
+    pub fn options_mut(&mut self) -> Option<&mut Vec<String>> {
+        match self {
+            Self::MultipleChoice(data) => Some(&mut data.options),
+            Self::Checkboxes(data) => Some(&mut data.options),
+            Self::Dropdown(data) => Some(&mut data.options),
+            _ => None,
         }
     }
-}
 
-fn edit_grid(ui: &mut egui::Ui, rows: &mut Vec<String>, columns: &mut Vec<String>) {
-    ui.horizontal(|ui| {
-        ui.vertical(|ui| edit_options(ui, rows, "Row"));
-        ui.vertical(|ui| edit_options(ui, columns, "Column"));
-    });
-}
+    /// Pictures shown with the options, for question types that have them.
+    pub fn option_images(&self) -> Option<&Vec<Option<crate::image::Image>>> {
+        match self {
+            Self::MultipleChoice(data) => Some(&data.option_images),
+            Self::Checkboxes(data) => Some(&data.option_images),
+            _ => None,
+        }
+    }
 
-fn edit_options(ui: &mut egui::Ui, options: &mut Vec<String>, label: impl Into<String>) {
-    let label: String = label.into();
-    ui.label(format!("{}s", label));
-    let mut delete_option = None;
-    for (i, option) in options.iter_mut().enumerate() {
-        ui.horizontal(|ui| {
-            ui.add(egui::TextEdit::singleline(option).desired_width(100.0));
-            if ui.button("❌").clicked() {
-                delete_option = Some(i);
-            }
-        });
+    pub fn option_images_mut(&mut self) -> Option<&mut Vec<Option<crate::image::Image>>> {
+        match self {
+            Self::MultipleChoice(data) => Some(&mut data.option_images),
+            Self::Checkboxes(data) => Some(&mut data.option_images),
+            _ => None,
+        }
     }
-    if let Some(i) = delete_option {
-        options.remove(i);
+
+    /// The rows and columns of a grid question, or `None` for other types.
+    pub fn grid(&self) -> Option<(&Vec<String>, &Vec<String>)> {
+        match self {
+            Self::MultipleChoiceGrid(data) => Some((&data.rows, &data.columns)),
+            Self::CheckboxGrid(data) => Some((&data.rows, &data.columns)),
+            _ => None,
+        }
     }
-    if ui.button(format!("Add {}", label.to_lowercase())).clicked() {
-        options.push(format!("{} {}", label, options.len() + 1));
+
+    pub fn grid_mut(&mut self) -> Option<(&mut Vec<String>, &mut Vec<String>)> {
+        match self {
+            Self::MultipleChoiceGrid(data) => Some((&mut data.rows, &mut data.columns)),
+            Self::CheckboxGrid(data) => Some((&mut data.rows, &mut data.columns)),
+            _ => None,
+        }
     }
 }
@@ -1,32 +1,50 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+use super::value_types::{DateValue, TimeValue};
+use crate::locale::Locale;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ShortAnswerConfig;
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ParagraphConfig;
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct MultipleChoiceConfig {
     pub options: Vec<String>,
+    /// Pictures shown with the options, by option index.
+    #[serde(default)]
+    pub option_images: Vec<Option<crate::image::Image>>,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct CheckboxesConfig {
     pub options: Vec<String>,
+    /// Pictures shown with the options, by option index.
+    #[serde(default)]
+    pub option_images: Vec<Option<crate::image::Image>>,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct DropdownConfig {
     pub options: Vec<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LinearScaleConfig {
-    pub start: usize,
+    pub start: i64,
     pub start_label: String,
-    pub end: usize,
+    pub end: i64,
     pub end_label: String,
+    /// Distance between neighbouring points on the scale.
+    #[serde(default = "default_step")]
+    pub step: i64,
+    #[serde(default)]
+    pub style: ScaleStyle,
+}
+
+fn default_step() -> i64 {
+    1
 }
 
 impl Default for LinearScaleConfig {
@@ -36,24 +54,419 @@ impl Default for LinearScaleConfig {
             end: 5,
             start_label: String::new(),
             end_label: String::new(),
+            step: 1,
+            style: ScaleStyle::default(),
+        }
+    }
+}
+
+impl LinearScaleConfig {
+    /// The points on the scale, from `start` up to at most `end`.
+    pub fn points(&self) -> impl Iterator<Item = i64> {
+        (self.start..=self.end).step_by(self.step.max(1) as usize)
+    }
+
+    /// A Net Promoter Score question: how likely, from 0 to 10, the
+    /// respondent is to recommend something.
+    pub fn net_promoter_score() -> Self {
+        Self {
+            start: 0,
+            end: 10,
+            start_label: "Not at all likely".into(),
+            end_label: "Extremely likely".into(),
+            step: 1,
+            style: ScaleStyle::NetPromoterScore,
+        }
+    }
+}
+
+/// How a linear scale is shown to respondents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ScaleStyle {
+    /// A row of numbered radio buttons.
+    #[default]
+    Numbers,
+    Stars,
+    /// Faces from sad to happy.
+    Emoji,
+    Slider,
+    /// A 0 to 10 scale, summed up as detractors, passives and promoters.
+    NetPromoterScore,
+}
+
+impl ScaleStyle {
+    pub const ALL: [Self; 5] = [
+        Self::Numbers,
+        Self::Stars,
+        Self::Emoji,
+        Self::Slider,
+        Self::NetPromoterScore,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Numbers => "Numbers",
+            Self::Stars => "Stars",
+            Self::Emoji => "Faces",
+            Self::Slider => "Slider",
+            Self::NetPromoterScore => "Net Promoter Score",
         }
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct MultipleChoiceGridConfig {
     pub rows: Vec<String>,
     pub columns: Vec<String>,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct CheckboxGridConfig {
     pub rows: Vec<String>,
     pub columns: Vec<String>,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct DateConfig;
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SavedDateConfig")]
+pub struct DateConfig {
+    pub include_year: bool,
+    /// Earliest date that can be picked.
+    pub min: Option<DateValue>,
+    /// Latest date that can be picked.
+    pub max: Option<DateValue>,
+    /// Whether the answer starts at the current date rather than empty.
+    pub default_today: bool,
+    /// Whether a time of day is asked for along with the date.
+    pub include_time: bool,
+}
+
+impl Default for DateConfig {
+    fn default() -> Self {
+        Self {
+            include_year: true,
+            min: None,
+            max: None,
+            default_today: false,
+            include_time: false,
+        }
+    }
+}
+
+/// Date configs as saved, which had no options before they were added.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedDateConfig {
+    Options {
+        include_year: bool,
+        #[serde(default)]
+        min: Option<DateValue>,
+        #[serde(default)]
+        max: Option<DateValue>,
+        #[serde(default)]
+        default_today: bool,
+        #[serde(default)]
+        include_time: bool,
+    },
+    Legacy(()),
+}
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct TimeConfig;
+impl From<SavedDateConfig> for DateConfig {
+    fn from(saved: SavedDateConfig) -> Self {
+        match saved {
+            SavedDateConfig::Options {
+                include_year,
+                min,
+                max,
+                default_today,
+                include_time,
+            } => Self {
+                include_year,
+                min,
+                max,
+                default_today,
+                include_time,
+            },
+            SavedDateConfig::Legacy(()) => Self::default(),
+        }
+    }
+}
+
+impl DateConfig {
+    /// The answer as text in `locale`, with the time if it is asked for.
+    pub fn format(&self, value: &DateValue, locale: &Locale) -> String {
+        let date = locale.format_date(value, self.include_year);
+        if self.include_time {
+            format!("{} {}", date, locale.format_time(&value.time, false))
+        } else {
+            date
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SavedTimeConfig")]
+pub struct TimeConfig {
+    /// Whether times are entered on a 24-hour clock even if the form's
+    /// locale uses AM/PM.
+    pub twenty_four_hour: bool,
+    pub seconds: bool,
+    /// Whether the answer is a length of time rather than a time of day.
+    pub duration: bool,
+}
+
+/// Time configs as saved, which had no options before they were added.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedTimeConfig {
+    Options {
+        twenty_four_hour: bool,
+        seconds: bool,
+        duration: bool,
+    },
+    Legacy(()),
+}
+
+impl From<SavedTimeConfig> for TimeConfig {
+    fn from(saved: SavedTimeConfig) -> Self {
+        match saved {
+            SavedTimeConfig::Options {
+                twenty_four_hour,
+                seconds,
+                duration,
+            } => Self {
+                twenty_four_hour,
+                seconds,
+                duration,
+            },
+            SavedTimeConfig::Legacy(()) => Self::default(),
+        }
+    }
+}
+
+impl TimeConfig {
+    /// Whether times are shown on a 24-hour clock in `locale`.
+    pub fn uses_24_hour(&self, locale: &Locale) -> bool {
+        self.twenty_four_hour || locale.twenty_four_hour
+    }
+
+    /// The answer as text in `locale`.
+    pub fn format(&self, value: &TimeValue, locale: &Locale) -> String {
+        if self.duration {
+            value.format_duration()
+        } else {
+            value.format(self.uses_24_hour(locale), self.seconds)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileUploadConfig {
+    /// Lowercase extensions without the dot, or empty to accept any file.
+    pub allowed_extensions: Vec<String>,
+    /// Largest accepted file, in bytes.
+    pub max_file_size: u64,
+    pub max_files: usize,
+}
+
+impl Default for FileUploadConfig {
+    fn default() -> Self {
+        Self {
+            allowed_extensions: vec![],
+            max_file_size: 10 * 1024 * 1024,
+            max_files: 1,
+        }
+    }
+}
+
+impl FileUploadConfig {
+    /// Checks whether a file called `name` of `size` bytes may be uploaded.
+    pub fn check_file(&self, name: &str, size: u64) -> Result<(), String> {
+        if !self.allowed_extensions.is_empty() {
+            let extension = std::path::Path::new(name)
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if !self.allowed_extensions.contains(&extension) {
+                return Err(format!(
+                    "{} is not one of the allowed file types: {}",
+                    name,
+                    self.allowed_extensions.join(", ")
+                ));
+            }
+        }
+        if size > self.max_file_size {
+            return Err(format!(
+                "{} is larger than {}",
+                name,
+                super::value_types::size_text(self.max_file_size)
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NumberConfig {
+    /// Whether only whole numbers are accepted.
+    pub integer: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Answers must be a whole number of steps from `min`, or from 0 without
+    /// a minimum. 0 accepts any number.
+    pub step: f64,
+    /// Written after the number, like "kg".
+    pub unit: String,
+    /// Decimal places shown, for numbers that aren't whole.
+    pub decimals: usize,
+}
+
+impl Default for NumberConfig {
+    fn default() -> Self {
+        Self {
+            integer: false,
+            min: None,
+            max: None,
+            step: 0.0,
+            unit: String::new(),
+            decimals: 2,
+        }
+    }
+}
+
+impl NumberConfig {
+    /// `number` with this question's decimal places and unit.
+    pub fn format(&self, number: f64) -> String {
+        let decimals = if self.integer { 0 } else { self.decimals };
+        if self.unit.is_empty() {
+            format!("{:.*}", decimals, number)
+        } else {
+            format!("{:.*} {}", decimals, number, self.unit)
+        }
+    }
+
+    /// The number nearest to `number` that is a whole number of steps from
+    /// the minimum, and whole if only whole numbers are accepted.
+    pub fn snap(&self, number: f64) -> f64 {
+        let mut number = number;
+        if self.step > 0.0 {
+            let base = self.min.unwrap_or(0.0);
+            number = base + ((number - base) / self.step).round() * self.step;
+            if self.max.is_some_and(|max| number > max) {
+                number -= self.step;
+            }
+        }
+        if self.integer {
+            number = number.round();
+        }
+        number
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmailConfig;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhoneConfig {
+    /// Country calling code filled in for new answers, without the "+".
+    pub default_country_code: String,
+}
+
+impl Default for PhoneConfig {
+    fn default() -> Self {
+        Self {
+            default_country_code: "1".into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UrlConfig;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AddressConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::question::QuestionConfig;
+
+    #[test]
+    fn file_types_and_sizes() {
+        let config = FileUploadConfig {
+            allowed_extensions: vec!["pdf".into(), "png".into()],
+            max_file_size: 1024 * 1024,
+            max_files: 1,
+        };
+        assert_eq!(config.check_file("cv.pdf", 1000), Ok(()));
+        assert_eq!(config.check_file("Photo.PNG", 1024 * 1024), Ok(()));
+        assert_eq!(
+            config.check_file("notes.txt", 10),
+            Err("notes.txt is not one of the allowed file types: pdf, png".into())
+        );
+        assert!(config.check_file("pdf", 10).is_err());
+        assert_eq!(
+            config.check_file("scan.pdf", 1024 * 1024 + 1),
+            Err("scan.pdf is larger than 1.0 MB".into())
+        );
+        assert_eq!(FileUploadConfig::default().check_file("any", 0), Ok(()));
+    }
+
+    #[test]
+    fn loads_date_configs_saved_without_options() {
+        let json: QuestionConfig = serde_json::from_str(r#"{ "Date": null }"#).unwrap();
+        let ron: QuestionConfig = ron::from_str("Date(())").unwrap();
+        for config in [json, ron] {
+            let QuestionConfig::Date(config) = config else {
+                panic!("not a date config");
+            };
+            assert_eq!(config, DateConfig::default());
+            assert!(config.include_year);
+        }
+    }
+
+    #[test]
+    fn loads_date_configs_with_options() {
+        let json = r#"{ "include_year": false, "max": { "year": 0, "month": 12, "day": 24 } }"#;
+        let config: DateConfig = serde_json::from_str(json).unwrap();
+        assert!(!config.include_year);
+        assert_eq!(config.max.map(|max| (max.month, max.day)), Some((12, 24)));
+        assert!(config.min.is_none() && !config.include_time);
+    }
+
+    #[test]
+    fn loads_time_configs_saved_without_options() {
+        let json: QuestionConfig = serde_json::from_str(r#"{ "Time": null }"#).unwrap();
+        let ron: QuestionConfig = ron::from_str("Time(())").unwrap();
+        for config in [json, ron] {
+            let QuestionConfig::Time(config) = config else {
+                panic!("not a time config");
+            };
+            assert_eq!(config, TimeConfig::default());
+        }
+        let json = r#"{ "twenty_four_hour": true, "seconds": false, "duration": true }"#;
+        let config: TimeConfig = serde_json::from_str(json).unwrap();
+        assert!(config.twenty_four_hour && config.duration);
+    }
+
+    #[test]
+    fn scale_points() {
+        let scale = |start, end, step| LinearScaleConfig {
+            start,
+            end,
+            step,
+            ..Default::default()
+        };
+        assert_eq!(scale(1, 5, 1).points().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert_eq!(scale(-2, 2, 2).points().collect::<Vec<_>>(), [-2, 0, 2]);
+        // The end is left off when it isn't a whole number of steps away.
+        assert_eq!(scale(1, 6, 2).points().collect::<Vec<_>>(), [1, 3, 5]);
+        // Saves with a step of 0 don't loop forever.
+        assert_eq!(scale(0, 2, 0).points().count(), 3);
+        assert_eq!(
+            LinearScaleConfig::net_promoter_score()
+                .points()
+                .collect::<Vec<_>>(),
+            (0..=10).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use eframe::egui;
+
+use super::config_types::*;
+use super::question_type::QuestionType;
+use super::registry::{self, CustomData, CustomQuestion};
+use super::value_types::*;
+use super::{QuestionConfig, QuestionValue};
+use crate::locale::Locale;
+
+/// A question's config together with its current answer. Each variant pairs a
+/// config with the value type of that same question type, so the two can't
+/// get out of step.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuestionKind {
+    ShortAnswer(ShortAnswerConfig, ShortAnswerValue),
+    Paragraph(ParagraphConfig, ParagraphValue),
+    MultipleChoice(MultipleChoiceConfig, MultipleChoiceValue),
+    Checkboxes(CheckboxesConfig, CheckboxesValue),
+    Dropdown(DropdownConfig, DropdownValue),
+    LinearScale(LinearScaleConfig, LinearScaleValue),
+    MultipleChoiceGrid(MultipleChoiceGridConfig, MultipleChoiceGridValue),
+    CheckboxGrid(CheckboxGridConfig, CheckboxGridValue),
+    Date(DateConfig, DateValue),
+    Time(TimeConfig, Option<TimeValue>),
+    FileUpload(FileUploadConfig, FileUploadValue),
+    Number(NumberConfig, NumberValue),
+    Email(EmailConfig, EmailValue),
+    Phone(PhoneConfig, PhoneValue),
+    Url(UrlConfig, UrlValue),
+    Address(AddressConfig, AddressValue),
+    Custom(CustomQuestion),
+}
+
+impl QuestionKind {
+    /// Pairs `config` with the default answer for it.
+    pub fn new(config: QuestionConfig) -> Self {
+        match config {
+            QuestionConfig::ShortAnswer(c) => with_default(c, Self::ShortAnswer),
+            QuestionConfig::Paragraph(c) => with_default(c, Self::Paragraph),
+            QuestionConfig::MultipleChoice(c) => with_default(c, Self::MultipleChoice),
+            QuestionConfig::Checkboxes(c) => with_default(c, Self::Checkboxes),
+            QuestionConfig::Dropdown(c) => with_default(c, Self::Dropdown),
+            QuestionConfig::LinearScale(c) => with_default(c, Self::LinearScale),
+            QuestionConfig::MultipleChoiceGrid(c) => with_default(c, Self::MultipleChoiceGrid),
+            QuestionConfig::CheckboxGrid(c) => with_default(c, Self::CheckboxGrid),
+            QuestionConfig::Date(c) => with_default(c, Self::Date),
+            QuestionConfig::Time(c) => with_default(c, Self::Time),
+            QuestionConfig::FileUpload(c) => with_default(c, Self::FileUpload),
+            QuestionConfig::Number(c) => with_default(c, Self::Number),
+            QuestionConfig::Email(c) => with_default(c, Self::Email),
+            QuestionConfig::Phone(c) => with_default(c, Self::Phone),
+            QuestionConfig::Url(c) => with_default(c, Self::Url),
+            QuestionConfig::Address(c) => with_default(c, Self::Address),
+            QuestionConfig::Custom(c) => Self::Custom(CustomQuestion {
+                value: registry::find(&c.tag)
+                    .map_or(serde_json::Value::Null, |t| t.default_value(&c.data)),
+                tag: c.tag,
+                config: c.data,
+            }),
+        }
+    }
+
+    /// Pairs `config` with `value`, falling back to the default answer if the
+    /// value belongs to a different question type.
+    pub fn from_parts(config: QuestionConfig, value: QuestionValue) -> Self {
+        match (config, value) {
+            (QuestionConfig::ShortAnswer(c), QuestionValue::ShortAnswer(v)) => {
+                Self::ShortAnswer(c, v)
+            }
+            (QuestionConfig::Paragraph(c), QuestionValue::Paragraph(v)) => Self::Paragraph(c, v),
+            (QuestionConfig::MultipleChoice(c), QuestionValue::MultipleChoice(v)) => {
+                Self::MultipleChoice(c, v)
+            }
+            (QuestionConfig::Checkboxes(c), QuestionValue::Checkboxes(v)) => Self::Checkboxes(c, v),
+            (QuestionConfig::Dropdown(c), QuestionValue::Dropdown(v)) => Self::Dropdown(c, v),
+            (QuestionConfig::LinearScale(c), QuestionValue::LinearScale(v)) => {
+                Self::LinearScale(c, v)
+            }
+            (QuestionConfig::MultipleChoiceGrid(c), QuestionValue::MultipleChoiceGrid(v)) => {
+                Self::MultipleChoiceGrid(c, v)
+            }
+            (QuestionConfig::CheckboxGrid(c), QuestionValue::CheckboxGrid(v)) => {
+                Self::CheckboxGrid(c, v)
+            }
+            (QuestionConfig::Date(c), QuestionValue::Date(v)) => Self::Date(c, v),
+            (QuestionConfig::Time(c), QuestionValue::Time(v)) => Self::Time(c, v),
+            (QuestionConfig::FileUpload(c), QuestionValue::FileUpload(v)) => Self::FileUpload(c, v),
+            (QuestionConfig::Number(c), QuestionValue::Number(v)) => Self::Number(c, v),
+            (QuestionConfig::Email(c), QuestionValue::Email(v)) => Self::Email(c, v),
+            (QuestionConfig::Phone(c), QuestionValue::Phone(v)) => Self::Phone(c, v),
+            (QuestionConfig::Url(c), QuestionValue::Url(v)) => Self::Url(c, v),
+            (QuestionConfig::Address(c), QuestionValue::Address(v)) => Self::Address(c, v),
+            (QuestionConfig::Custom(c), QuestionValue::Custom(v)) if c.tag == v.tag => {
+                Self::Custom(CustomQuestion {
+                    tag: c.tag,
+                    config: c.data,
+                    value: v.data,
+                })
+            }
+            (config, _) => Self::new(config),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ShortAnswer(..) => ShortAnswerConfig::NAME,
+            Self::Paragraph(..) => ParagraphConfig::NAME,
+            Self::MultipleChoice(..) => MultipleChoiceConfig::NAME,
+            Self::Checkboxes(..) => CheckboxesConfig::NAME,
+            Self::Dropdown(..) => DropdownConfig::NAME,
+            Self::LinearScale(..) => LinearScaleConfig::NAME,
+            Self::MultipleChoiceGrid(..) => MultipleChoiceGridConfig::NAME,
+            Self::CheckboxGrid(..) => CheckboxGridConfig::NAME,
+            Self::Date(..) => DateConfig::NAME,
+            Self::Time(..) => TimeConfig::NAME,
+            Self::FileUpload(..) => FileUploadConfig::NAME,
+            Self::Number(..) => NumberConfig::NAME,
+            Self::Email(..) => EmailConfig::NAME,
+            Self::Phone(..) => PhoneConfig::NAME,
+            Self::Url(..) => UrlConfig::NAME,
+            Self::Address(..) => AddressConfig::NAME,
+            Self::Custom(c) => registry::find(&c.tag).map_or("Unknown type", |t| t.name()),
+        }
+    }
+
+    pub fn config(&self) -> QuestionConfig {
+        match self {
+            Self::ShortAnswer(c, _) => QuestionConfig::ShortAnswer(c.clone()),
+            Self::Paragraph(c, _) => QuestionConfig::Paragraph(c.clone()),
+            Self::MultipleChoice(c, _) => QuestionConfig::MultipleChoice(c.clone()),
+            Self::Checkboxes(c, _) => QuestionConfig::Checkboxes(c.clone()),
+            Self::Dropdown(c, _) => QuestionConfig::Dropdown(c.clone()),
+            Self::LinearScale(c, _) => QuestionConfig::LinearScale(c.clone()),
+            Self::MultipleChoiceGrid(c, _) => QuestionConfig::MultipleChoiceGrid(c.clone()),
+            Self::CheckboxGrid(c, _) => QuestionConfig::CheckboxGrid(c.clone()),
+            Self::Date(c, _) => QuestionConfig::Date(c.clone()),
+            Self::Time(c, _) => QuestionConfig::Time(c.clone()),
+            Self::FileUpload(c, _) => QuestionConfig::FileUpload(c.clone()),
+            Self::Number(c, _) => QuestionConfig::Number(c.clone()),
+            Self::Email(c, _) => QuestionConfig::Email(c.clone()),
+            Self::Phone(c, _) => QuestionConfig::Phone(c.clone()),
+            Self::Url(c, _) => QuestionConfig::Url(c.clone()),
+            Self::Address(c, _) => QuestionConfig::Address(c.clone()),
+            Self::Custom(c) => QuestionConfig::Custom(CustomData {
+                tag: c.tag.clone(),
+                data: c.config.clone(),
+            }),
+        }
+    }
+
+    pub fn value(&self) -> QuestionValue {
+        match self {
+            Self::ShortAnswer(_, v) => QuestionValue::ShortAnswer(v.clone()),
+            Self::Paragraph(_, v) => QuestionValue::Paragraph(v.clone()),
+            Self::MultipleChoice(_, v) => QuestionValue::MultipleChoice(v.clone()),
+            Self::Checkboxes(_, v) => QuestionValue::Checkboxes(v.clone()),
+            Self::Dropdown(_, v) => QuestionValue::Dropdown(v.clone()),
+            Self::LinearScale(_, v) => QuestionValue::LinearScale(v.clone()),
+            Self::MultipleChoiceGrid(_, v) => QuestionValue::MultipleChoiceGrid(v.clone()),
+            Self::CheckboxGrid(_, v) => QuestionValue::CheckboxGrid(v.clone()),
+            Self::Date(_, v) => QuestionValue::Date(v.clone()),
+            Self::Time(_, v) => QuestionValue::Time(v.clone()),
+            Self::FileUpload(_, v) => QuestionValue::FileUpload(v.clone()),
+            Self::Number(_, v) => QuestionValue::Number(v.clone()),
+            Self::Email(_, v) => QuestionValue::Email(v.clone()),
+            Self::Phone(_, v) => QuestionValue::Phone(v.clone()),
+            Self::Url(_, v) => QuestionValue::Url(v.clone()),
+            Self::Address(_, v) => QuestionValue::Address(v.clone()),
+            Self::Custom(c) => QuestionValue::Custom(CustomData {
+                tag: c.tag.clone(),
+                data: c.value.clone(),
+            }),
+        }
+    }
+
+    pub fn reset_value(&mut self) {
+        match self {
+            Self::ShortAnswer(c, v) => *v = c.default_value(),
+            Self::Paragraph(c, v) => *v = c.default_value(),
+            Self::MultipleChoice(c, v) => *v = c.default_value(),
+            Self::Checkboxes(c, v) => *v = c.default_value(),
+            Self::Dropdown(c, v) => *v = c.default_value(),
+            Self::LinearScale(c, v) => *v = c.default_value(),
+            Self::MultipleChoiceGrid(c, v) => *v = c.default_value(),
+            Self::CheckboxGrid(c, v) => *v = c.default_value(),
+            Self::Date(c, v) => *v = c.default_value(),
+            Self::Time(c, v) => *v = c.default_value(),
+            Self::FileUpload(c, v) => *v = c.default_value(),
+            Self::Number(c, v) => *v = c.default_value(),
+            Self::Email(c, v) => *v = c.default_value(),
+            Self::Phone(c, v) => *v = c.default_value(),
+            Self::Url(c, v) => *v = c.default_value(),
+            Self::Address(c, v) => *v = c.default_value(),
+            Self::Custom(c) => {
+                if let Some(t) = registry::find(&c.tag) {
+                    c.value = t.default_value(&c.config);
+                }
+            }
+        }
+    }
+
+    pub fn edit(&mut self, ui: &mut egui::Ui, locale: &Locale) {
+        match self {
+            Self::ShortAnswer(c, _) => c.edit(ui, locale),
+            Self::Paragraph(c, _) => c.edit(ui, locale),
+            Self::MultipleChoice(c, _) => c.edit(ui, locale),
+            Self::Checkboxes(c, _) => c.edit(ui, locale),
+            Self::Dropdown(c, _) => c.edit(ui, locale),
+            Self::LinearScale(c, _) => c.edit(ui, locale),
+            Self::MultipleChoiceGrid(c, _) => c.edit(ui, locale),
+            Self::CheckboxGrid(c, _) => c.edit(ui, locale),
+            Self::Date(c, _) => c.edit(ui, locale),
+            Self::Time(c, _) => c.edit(ui, locale),
+            Self::FileUpload(c, _) => c.edit(ui, locale),
+            Self::Number(c, _) => c.edit(ui, locale),
+            Self::Email(c, _) => c.edit(ui, locale),
+            Self::Phone(c, _) => c.edit(ui, locale),
+            Self::Url(c, _) => c.edit(ui, locale),
+            Self::Address(c, _) => c.edit(ui, locale),
+            Self::Custom(c) => match registry::find(&c.tag) {
+                Some(t) => t.edit(ui, &mut c.config, locale),
+                None => unknown_type(ui, &c.tag),
+            },
+        }
+    }
+
+    pub fn preview(&mut self, ui: &mut egui::Ui, locale: &Locale) {
+        match self {
+            Self::ShortAnswer(c, v) => c.preview(ui, v, locale),
+            Self::Paragraph(c, v) => c.preview(ui, v, locale),
+            Self::MultipleChoice(c, v) => c.preview(ui, v, locale),
+            Self::Checkboxes(c, v) => c.preview(ui, v, locale),
+            Self::Dropdown(c, v) => c.preview(ui, v, locale),
+            Self::LinearScale(c, v) => c.preview(ui, v, locale),
+            Self::MultipleChoiceGrid(c, v) => c.preview(ui, v, locale),
+            Self::CheckboxGrid(c, v) => c.preview(ui, v, locale),
+            Self::Date(c, v) => c.preview(ui, v, locale),
+            Self::Time(c, v) => c.preview(ui, v, locale),
+            Self::FileUpload(c, v) => c.preview(ui, v, locale),
+            Self::Number(c, v) => c.preview(ui, v, locale),
+            Self::Email(c, v) => c.preview(ui, v, locale),
+            Self::Phone(c, v) => c.preview(ui, v, locale),
+            Self::Url(c, v) => c.preview(ui, v, locale),
+            Self::Address(c, v) => c.preview(ui, v, locale),
+            Self::Custom(c) => match registry::find(&c.tag) {
+                Some(t) => t.preview(ui, &c.config, &mut c.value, locale),
+                None => unknown_type(ui, &c.tag),
+            },
+        }
+    }
+
+    pub fn validate(&self, locale: &Locale) -> Result<(), String> {
+        match self {
+            Self::ShortAnswer(c, v) => c.validate(v, locale),
+            Self::Paragraph(c, v) => c.validate(v, locale),
+            Self::MultipleChoice(c, v) => c.validate(v, locale),
+            Self::Checkboxes(c, v) => c.validate(v, locale),
+            Self::Dropdown(c, v) => c.validate(v, locale),
+            Self::LinearScale(c, v) => c.validate(v, locale),
+            Self::MultipleChoiceGrid(c, v) => c.validate(v, locale),
+            Self::CheckboxGrid(c, v) => c.validate(v, locale),
+            Self::Date(c, v) => c.validate(v, locale),
+            Self::Time(c, v) => c.validate(v, locale),
+            Self::FileUpload(c, v) => c.validate(v, locale),
+            Self::Number(c, v) => c.validate(v, locale),
+            Self::Email(c, v) => c.validate(v, locale),
+            Self::Phone(c, v) => c.validate(v, locale),
+            Self::Url(c, v) => c.validate(v, locale),
+            Self::Address(c, v) => c.validate(v, locale),
+            Self::Custom(c) => match registry::find(&c.tag) {
+                Some(t) => t.validate(&c.config, &c.value, locale),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// `value` as text for exports, with dates and times in `locale`, or
+    /// `None` if it is an answer to a different type of question.
+    pub fn answer_text(&self, value: &QuestionValue, locale: &Locale) -> Option<String> {
+        Some(match (self, value) {
+            (Self::ShortAnswer(c, _), QuestionValue::ShortAnswer(v)) => c.answer_text(v, locale),
+            (Self::Paragraph(c, _), QuestionValue::Paragraph(v)) => c.answer_text(v, locale),
+            (Self::MultipleChoice(c, _), QuestionValue::MultipleChoice(v)) => {
+                c.answer_text(v, locale)
+            }
+            (Self::Checkboxes(c, _), QuestionValue::Checkboxes(v)) => c.answer_text(v, locale),
+            (Self::Dropdown(c, _), QuestionValue::Dropdown(v)) => c.answer_text(v, locale),
+            (Self::LinearScale(c, _), QuestionValue::LinearScale(v)) => c.answer_text(v, locale),
+            (Self::MultipleChoiceGrid(c, _), QuestionValue::MultipleChoiceGrid(v)) => {
+                c.answer_text(v, locale)
+            }
+            (Self::CheckboxGrid(c, _), QuestionValue::CheckboxGrid(v)) => c.answer_text(v, locale),
+            (Self::Date(c, _), QuestionValue::Date(v)) => c.answer_text(v, locale),
+            (Self::Time(c, _), QuestionValue::Time(v)) => c.answer_text(v, locale),
+            (Self::FileUpload(c, _), QuestionValue::FileUpload(v)) => c.answer_text(v, locale),
+            (Self::Number(c, _), QuestionValue::Number(v)) => c.answer_text(v, locale),
+            (Self::Email(c, _), QuestionValue::Email(v)) => c.answer_text(v, locale),
+            (Self::Phone(c, _), QuestionValue::Phone(v)) => c.answer_text(v, locale),
+            (Self::Url(c, _), QuestionValue::Url(v)) => c.answer_text(v, locale),
+            (Self::Address(c, _), QuestionValue::Address(v)) => c.answer_text(v, locale),
+            (Self::Custom(c), QuestionValue::Custom(v)) if c.tag == v.tag => {
+                match registry::find(&c.tag) {
+                    Some(t) => t.answer_text(&c.config, &v.data, locale),
+                    None => v.data.to_string(),
+                }
+            }
+            _ => return None,
+        })
+    }
+}
+
+fn with_default<T: QuestionType>(config: T, kind: fn(T, T::Value) -> QuestionKind) -> QuestionKind {
+    let value = config.default_value();
+    kind(config, value)
+}
+
+/// Stands in for a custom question whose type isn't registered, keeping its data
+/// untouched so that nothing is lost when the type comes back.
+fn unknown_type(ui: &mut egui::Ui, tag: &str) {
+    ui.label(format!("Question type \"{}\" is not available", tag));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkboxes_config() -> CheckboxesConfig {
+        CheckboxesConfig {
+            options: vec!["Red".into(), "Blue".into()],
+            option_images: vec![],
+        }
+    }
+
+    fn checkboxes() -> QuestionConfig {
+        QuestionConfig::Checkboxes(checkboxes_config())
+    }
+
+    #[test]
+    fn from_parts_keeps_matching_value() {
+        let value = CheckboxesValue {
+            choices: vec![false, true],
+        };
+        let kind = QuestionKind::from_parts(checkboxes(), QuestionValue::Checkboxes(value.clone()));
+        assert_eq!(kind, QuestionKind::Checkboxes(checkboxes_config(), value));
+    }
+
+    #[test]
+    fn from_parts_replaces_mismatched_value() {
+        let value = QuestionValue::ShortAnswer(ShortAnswerValue { text: "Red".into() });
+        let kind = QuestionKind::from_parts(checkboxes(), value);
+        assert_eq!(
+            kind,
+            QuestionKind::Checkboxes(
+                checkboxes_config(),
+                CheckboxesValue {
+                    choices: vec![false, false]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn from_parts_replaces_value_of_other_custom_type() {
+        let config = QuestionConfig::Custom(CustomData {
+            tag: "rating".into(),
+            data: serde_json::json!({ "stars": 5 }),
+        });
+        let value = QuestionValue::Custom(CustomData {
+            tag: "signature".into(),
+            data: serde_json::json!("Ada"),
+        });
+        let kind = QuestionKind::from_parts(config.clone(), value);
+        assert_eq!(kind, QuestionKind::new(config));
+    }
+}
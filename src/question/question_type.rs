@@ -0,0 +1,1267 @@
+use std::fmt::Debug;
+
+use eframe::egui;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{config_types, value_types};
+use crate::image::Image;
+use crate::locale::Locale;
+
+/// Behaviour of a question type, implemented on its config so that each config
+/// can only ever be paired with its own kind of value.
+pub trait QuestionType:
+    Clone + Debug + Default + PartialEq + Serialize + DeserializeOwned + 'static
+{
+    type Value: Clone + Debug + Default + PartialEq + Serialize + DeserializeOwned;
+
+    /// Name shown in the question type menu.
+    const NAME: &'static str;
+
+    /// Shows the controls for the config under the question name in the editor.
+    fn edit(&mut self, _ui: &mut egui::Ui, _locale: &Locale) {}
+
+    /// The answer a respondent starts with.
+    fn default_value(&self) -> Self::Value;
+
+    /// Shows the input for answering the question.
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, locale: &Locale);
+
+    /// Checks an answer on submit, returning the message to show the respondent
+    /// if it can't be accepted.
+    fn validate(&self, _value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The answer as text, as listed in response summaries and exports.
+    fn answer_text(&self, value: &Self::Value, locale: &Locale) -> String;
+}
+
+/// A question type defined outside of eform. Register it with
+/// [`register_question_type`](super::register_question_type) to add it to the
+/// type menu.
+pub trait CustomQuestionType: QuestionType {
+    /// Identifies the type in save files, so it must not change between versions.
+    const TAG: &'static str;
+}
+
+impl QuestionType for config_types::ShortAnswerConfig {
+    type Value = value_types::ShortAnswerValue;
+
+    const NAME: &'static str = "Short answer";
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        ui.add(egui::TextEdit::singleline(&mut value.text).hint_text("Your answer"));
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.text.clone()
+    }
+}
+
+impl QuestionType for config_types::ParagraphConfig {
+    type Value = value_types::ParagraphValue;
+
+    const NAME: &'static str = "Paragraph";
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        ui.add(egui::TextEdit::multiline(&mut value.text).hint_text("Your answer"));
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.text.clone()
+    }
+}
+
+impl QuestionType for config_types::MultipleChoiceConfig {
+    type Value = value_types::MultipleChoiceValue;
+
+    const NAME: &'static str = "Multiple choice";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        edit_options(
+            ui,
+            &mut self.options,
+            Some(&mut self.option_images),
+            "Option",
+        );
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        for (i, option) in self.options.iter().enumerate() {
+            ui.radio_value(&mut value.choice, option.clone(), option);
+            show_option_image(ui, &self.option_images, i);
+        }
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.choice.clone()
+    }
+}
+
+impl QuestionType for config_types::CheckboxesConfig {
+    type Value = value_types::CheckboxesValue;
+
+    const NAME: &'static str = "Checkboxes";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        edit_options(
+            ui,
+            &mut self.options,
+            Some(&mut self.option_images),
+            "Option",
+        );
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value {
+            choices: vec![false; self.options.len()],
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        value.choices.resize(self.options.len(), false);
+        for (i, option) in self.options.iter().enumerate() {
+            ui.checkbox(&mut value.choices[i], option);
+            show_option_image(ui, &self.option_images, i);
+        }
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        let chosen: Vec<&str> = self
+            .options
+            .iter()
+            .zip(value.choices.iter())
+            .filter(|(_, chosen)| **chosen)
+            .map(|(option, _)| option.as_str())
+            .collect();
+        chosen.join("; ")
+    }
+}
+
+impl QuestionType for config_types::DropdownConfig {
+    type Value = value_types::DropdownValue;
+
+    const NAME: &'static str = "Dropdown";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        edit_options(ui, &mut self.options, None, "Option");
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        let label = if value.choice.is_empty() {
+            "Choose".into()
+        } else {
+            value.choice.clone()
+        };
+        ui.menu_button(label, |ui| {
+            for option in self.options.iter() {
+                if ui
+                    .selectable_value(&mut value.choice, option.clone(), option)
+                    .clicked()
+                {
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.choice.clone()
+    }
+}
+
+impl QuestionType for config_types::LinearScaleConfig {
+    type Value = value_types::LinearScaleValue;
+
+    const NAME: &'static str = "Linear scale";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        egui::ComboBox::from_id_source(ui.next_auto_id())
+            .selected_text(self.style.name())
+            .show_ui(ui, |ui| {
+                for style in config_types::ScaleStyle::ALL {
+                    if ui
+                        .selectable_value(&mut self.style, style, style.name())
+                        .clicked()
+                        && style == config_types::ScaleStyle::NetPromoterScore
+                    {
+                        *self = Self::net_promoter_score();
+                    }
+                }
+            });
+        // A Net Promoter Score is always 0 to 10.
+        let is_nps = self.style == config_types::ScaleStyle::NetPromoterScore;
+        ui.add_enabled_ui(!is_nps, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.start).clamp_range(-100..=self.end - 1));
+                ui.label("to");
+                ui.add(egui::DragValue::new(&mut self.end).clamp_range(self.start + 1..=1000));
+                ui.label("in steps of");
+                let range = self.end - self.start;
+                ui.add(egui::DragValue::new(&mut self.step).clamp_range(1..=range));
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("{}", self.start));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.start_label)
+                    .hint_text("Label (optional)")
+                    .desired_width(100.0),
+            );
+        });
+        let last = self.points().last().unwrap_or(self.end);
+        if last != self.end {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "{} is not a step from {}, so the scale ends at {}",
+                    self.end, self.start, last
+                ),
+            );
+        }
+        ui.horizontal(|ui| {
+            ui.label(format!("{}", last));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.end_label)
+                    .hint_text("Label (optional)")
+                    .desired_width(100.0),
+            );
+        });
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value { value: self.start }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        // Saves from elsewhere can have the start past the end.
+        if self.points().next().is_none() {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!("The scale from {} to {} is empty", self.start, self.end),
+            );
+            return;
+        }
+        ui.horizontal(|ui| {
+            if !self.start_label.is_empty() {
+                ui.label(&self.start_label);
+            }
+            match self.style {
+                config_types::ScaleStyle::Numbers | config_types::ScaleStyle::NetPromoterScore => {
+                    for point in self.points() {
+                        ui.radio_value(&mut value.value, point, point.to_string());
+                    }
+                }
+                config_types::ScaleStyle::Stars => {
+                    for point in self.points() {
+                        let star = if point <= value.value { "★" } else { "☆" };
+                        let text = egui::RichText::new(star).size(20.0);
+                        if ui.add(egui::Button::new(text).frame(false)).clicked() {
+                            value.value = point;
+                        }
+                    }
+                }
+                config_types::ScaleStyle::Emoji => {
+                    let count = self.points().count();
+                    for (i, point) in self.points().enumerate() {
+                        let face = FACES[i * (FACES.len() - 1) / (count - 1).max(1)];
+                        let text = egui::RichText::new(face).size(20.0);
+                        ui.selectable_value(&mut value.value, point, text)
+                            .on_hover_text(point.to_string());
+                    }
+                }
+                config_types::ScaleStyle::Slider => {
+                    // Slide over the points' indices, so every stop is on the scale.
+                    let (start, step) = (self.start, self.step.max(1));
+                    let last = self.points().count() as i64 - 1;
+                    let mut index = ((value.value - start) / step).clamp(0, last);
+                    let slider = egui::Slider::new(&mut index, 0..=last)
+                        .custom_formatter(|index, _| (start + index as i64 * step).to_string())
+                        .custom_parser(|text| {
+                            let point = text.trim().parse::<f64>().ok()?;
+                            Some(((point - start as f64) / step as f64).round())
+                        });
+                    if ui.add(slider).changed() {
+                        value.value = start + index * step;
+                    }
+                }
+            }
+            if !self.end_label.is_empty() {
+                ui.label(&self.end_label);
+            }
+        });
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        if self.points().any(|point| point == value.value) {
+            Ok(())
+        } else {
+            Err(format!("{} is not on the scale", value.value))
+        }
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.value.to_string()
+    }
+}
+
+impl QuestionType for config_types::NumberConfig {
+    type Value = value_types::NumberValue;
+
+    const NAME: &'static str = "Number";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        ui.checkbox(&mut self.integer, "Whole numbers only");
+        for (limit, label) in [(&mut self.min, "Minimum"), (&mut self.max, "Maximum")] {
+            ui.horizontal(|ui| {
+                let mut limited = limit.is_some();
+                if ui.checkbox(&mut limited, label).changed() {
+                    *limit = limited.then_some(0.0);
+                }
+                if let Some(limit) = limit {
+                    ui.add(egui::DragValue::new(limit));
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Step");
+            ui.add(
+                egui::DragValue::new(&mut self.step)
+                    .clamp_range(0.0..=f64::MAX)
+                    .speed(0.1),
+            )
+            .on_hover_text("0 accepts any number");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Unit");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.unit)
+                    .hint_text("e.g. kg")
+                    .desired_width(60.0),
+            );
+        });
+        ui.add_enabled_ui(!self.integer, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Decimal places");
+                ui.add(egui::DragValue::new(&mut self.decimals).clamp_range(0..=10));
+            });
+        });
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        let decimals = if self.integer { 0 } else { self.decimals };
+        let start = value
+            .value
+            .unwrap_or_else(|| self.min.unwrap_or(0.0).max(0.0));
+        let mut number = start;
+        ui.horizontal(|ui| {
+            let speed = if self.step > 0.0 { self.step } else { 1.0 };
+            let mut drag = egui::DragValue::new(&mut number)
+                .speed(speed)
+                .clamp_range(self.min.unwrap_or(f64::MIN)..=self.max.unwrap_or(f64::MAX))
+                .max_decimals(decimals);
+            if !self.unit.is_empty() {
+                drag = drag.suffix(format!(" {}", self.unit));
+            }
+            if ui.add(drag).changed() {
+                value.value = Some(self.snap(number));
+            }
+            if value.value.is_none() {
+                ui.weak("No answer");
+            } else if ui.small_button("❌").clicked() {
+                value.value = None;
+            }
+        });
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        let Some(number) = value.value else {
+            return Ok(());
+        };
+        if !number.is_finite() {
+            return Err("Enter a number".into());
+        }
+        if self.integer && number.fract() != 0.0 {
+            return Err("Enter a whole number".into());
+        }
+        if let Some(min) = self.min {
+            if number < min {
+                return Err(format!("Enter {} or more", self.format(min)));
+            }
+        }
+        if let Some(max) = self.max {
+            if number > max {
+                return Err(format!("Enter {} or less", self.format(max)));
+            }
+        }
+        if self.step > 0.0 {
+            let steps = (number - self.min.unwrap_or(0.0)) / self.step;
+            if (steps - steps.round()).abs() > 1e-9 {
+                return Err(format!("Enter a number in steps of {}", self.step));
+            }
+        }
+        Ok(())
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value
+            .value
+            .map_or_else(String::new, |number| self.format(number))
+    }
+}
+
+impl QuestionType for config_types::EmailConfig {
+    type Value = value_types::EmailValue;
+
+    const NAME: &'static str = "Email";
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        let response =
+            ui.add(egui::TextEdit::singleline(&mut value.text).hint_text("name@example.com"));
+        if response.lost_focus() {
+            value.normalize();
+        }
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        if value.text.trim().is_empty() {
+            return Ok(());
+        }
+        value.check()
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.text.clone()
+    }
+}
+
+impl QuestionType for config_types::PhoneConfig {
+    type Value = value_types::PhoneValue;
+
+    const NAME: &'static str = "Phone number";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        ui.horizontal(|ui| {
+            ui.label("Default country code +");
+            ui.add(egui::TextEdit::singleline(&mut self.default_country_code).desired_width(40.0));
+        });
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value {
+            country_code: self.default_country_code.clone(),
+            number: String::new(),
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        ui.horizontal(|ui| {
+            ui.label("+");
+            let code = ui.add(
+                egui::TextEdit::singleline(&mut value.country_code)
+                    .hint_text("1")
+                    .desired_width(40.0),
+            );
+            let number = ui.add(
+                egui::TextEdit::singleline(&mut value.number)
+                    .hint_text("Phone number")
+                    .desired_width(140.0),
+            );
+            if code.lost_focus() || number.lost_focus() {
+                value.normalize();
+            }
+            if !value.is_empty() && value.check().is_ok() {
+                ui.weak(value.format());
+            }
+        });
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        if value.is_empty() {
+            return Ok(());
+        }
+        value.check()
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        if value.is_empty() {
+            String::new()
+        } else {
+            value.international()
+        }
+    }
+}
+
+impl QuestionType for config_types::UrlConfig {
+    type Value = value_types::UrlValue;
+
+    const NAME: &'static str = "Web address";
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        let response =
+            ui.add(egui::TextEdit::singleline(&mut value.text).hint_text("https://example.com"));
+        if response.lost_focus() {
+            value.normalize();
+        }
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        if value.text.trim().is_empty() {
+            return Ok(());
+        }
+        value.check()
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.text.clone()
+    }
+}
+
+impl QuestionType for config_types::AddressConfig {
+    type Value = value_types::AddressValue;
+
+    const NAME: &'static str = "Address";
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        let mut lost_focus = false;
+        egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            for (label, part) in [
+                ("Street", &mut value.street),
+                ("City", &mut value.city),
+                ("State / region", &mut value.region),
+                ("Postal code", &mut value.postal_code),
+                ("Country", &mut value.country),
+            ] {
+                ui.label(label);
+                lost_focus |= ui.text_edit_singleline(part).lost_focus();
+                ui.end_row();
+            }
+        });
+        if lost_focus {
+            value.normalize();
+        }
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        value.check()
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value.format()
+    }
+}
+
+/// Faces for the emoji scale style, from the lowest point to the highest.
+const FACES: [&str; 5] = ["😞", "🙁", "😐", "🙂", "😄"];
+
+impl QuestionType for config_types::MultipleChoiceGridConfig {
+    type Value = value_types::MultipleChoiceGridValue;
+
+    const NAME: &'static str = "Multiple choice grid";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        edit_grid(ui, &mut self.rows, &mut self.columns);
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value {
+            choices: vec![String::new(); self.rows.len()],
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        value.choices.resize(self.rows.len(), String::new());
+        egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.label("");
+            for column in self.columns.iter() {
+                ui.label(column);
+            }
+            ui.end_row();
+            for (y, row) in self.rows.iter().enumerate() {
+                ui.label(row);
+                for column in self.columns.iter() {
+                    ui.radio_value(&mut value.choices[y], column.clone(), "");
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .zip(value.choices.iter())
+            .filter(|(_, choice)| !choice.is_empty())
+            .map(|(row, choice)| format!("{}: {}", row, choice))
+            .collect();
+        rows.join("; ")
+    }
+}
+
+impl QuestionType for config_types::CheckboxGridConfig {
+    type Value = value_types::CheckboxGridValue;
+
+    const NAME: &'static str = "Checkbox grid";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        edit_grid(ui, &mut self.rows, &mut self.columns);
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value {
+            choices: vec![vec![false; self.columns.len()]; self.rows.len()],
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        value.choices.resize(self.rows.len(), vec![]);
+        for row in value.choices.iter_mut() {
+            row.resize(self.columns.len(), false);
+        }
+        egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.label("");
+            for column in self.columns.iter() {
+                ui.label(column);
+            }
+            ui.end_row();
+            for (y, row) in self.rows.iter().enumerate() {
+                ui.label(row);
+                for x in 0..self.columns.len() {
+                    ui.checkbox(&mut value.choices[y][x], "");
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .zip(value.choices.iter())
+            .filter(|(_, chosen)| chosen.contains(&true))
+            .map(|(row, chosen)| {
+                let columns: Vec<&str> = self
+                    .columns
+                    .iter()
+                    .zip(chosen.iter())
+                    .filter(|(_, chosen)| **chosen)
+                    .map(|(column, _)| column.as_str())
+                    .collect();
+                format!("{}: {}", row, columns.join(", "))
+            })
+            .collect();
+        rows.join("; ")
+    }
+}
+
+impl QuestionType for config_types::DateConfig {
+    type Value = value_types::DateValue;
+
+    const NAME: &'static str = "Date";
+
+    fn edit(&mut self, ui: &mut egui::Ui, locale: &Locale) {
+        ui.checkbox(&mut self.include_year, "Include year");
+        ui.checkbox(&mut self.default_today, "Start at today's date");
+        ui.checkbox(&mut self.include_time, "Include time");
+        for (limit, label) in [
+            (&mut self.min, "Earliest date"),
+            (&mut self.max, "Latest date"),
+        ] {
+            ui.horizontal(|ui| {
+                let mut limited = limit.is_some();
+                if ui.checkbox(&mut limited, label).changed() {
+                    *limit = limited.then(value_types::DateValue::today);
+                }
+                if let Some(date) = limit {
+                    crate::calendar::date_picker(ui, date, self.include_year, None, None, locale);
+                }
+            });
+        }
+    }
+
+    fn default_value(&self) -> Self::Value {
+        if self.default_today {
+            Self::Value::today()
+        } else {
+            Self::Value::default()
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, locale: &Locale) {
+        ui.horizontal(|ui| {
+            crate::calendar::date_picker(
+                ui,
+                value,
+                self.include_year,
+                self.min.as_ref(),
+                self.max.as_ref(),
+                locale,
+            );
+            if !value.is_empty() && ui.small_button("❌").clicked() {
+                *value = value_types::DateValue::default();
+            }
+        });
+        if self.include_time && !value.is_empty() {
+            time_input(ui, &mut value.time, locale.twenty_four_hour, false);
+        }
+    }
+
+    fn validate(&self, value: &Self::Value, locale: &Locale) -> Result<(), String> {
+        if value.is_empty() {
+            return Ok(());
+        }
+        if !value.is_valid(self.include_year) {
+            return Err(format!(
+                "{} is not a real date",
+                locale.format_date(value, self.include_year)
+            ));
+        }
+        let key = value.sort_key(self.include_year);
+        if let Some(min) = self.min.as_ref() {
+            if key < min.sort_key(self.include_year) {
+                return Err(format!(
+                    "Choose a date on or after {}",
+                    locale.format_date(min, self.include_year)
+                ));
+            }
+        }
+        if let Some(max) = self.max.as_ref() {
+            if key > max.sort_key(self.include_year) {
+                return Err(format!(
+                    "Choose a date on or before {}",
+                    locale.format_date(max, self.include_year)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn answer_text(&self, value: &Self::Value, locale: &Locale) -> String {
+        if value.is_empty() {
+            String::new()
+        } else {
+            self.format(value, locale)
+        }
+    }
+}
+
+impl QuestionType for config_types::TimeConfig {
+    type Value = Option<value_types::TimeValue>;
+
+    const NAME: &'static str = "Time";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        ui.checkbox(&mut self.duration, "Duration (time elapsed)");
+        ui.add_enabled_ui(!self.duration, |ui| {
+            ui.checkbox(&mut self.twenty_four_hour, "Always use 24-hour clock")
+                .on_hover_text("Otherwise the clock follows the form's locale");
+            ui.checkbox(&mut self.seconds, "Include seconds");
+        });
+    }
+
+    fn default_value(&self) -> Self::Value {
+        None
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, locale: &Locale) {
+        let Some(time) = value else {
+            let label = if self.duration {
+                "Enter a duration"
+            } else {
+                "Enter a time"
+            };
+            if ui.button(label).clicked() {
+                *value = Some(value_types::TimeValue::default());
+            }
+            return;
+        };
+        let cleared = ui.horizontal(|ui| {
+            if self.duration {
+                ui.add(egui::DragValue::new(&mut time.hour).suffix(" h"));
+                ui.add(
+                    egui::DragValue::new(&mut time.minute)
+                        .clamp_range(0..=59)
+                        .suffix(" min"),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut time.second)
+                        .clamp_range(0..=59)
+                        .suffix(" s"),
+                );
+            } else {
+                time_input(ui, time, self.uses_24_hour(locale), self.seconds);
+            }
+            ui.small_button("❌").clicked()
+        });
+        if cleared.inner {
+            *value = None;
+        }
+    }
+
+    fn answer_text(&self, value: &Self::Value, locale: &Locale) -> String {
+        value
+            .as_ref()
+            .map_or_else(String::new, |time| self.format(time, locale))
+    }
+}
+
+impl QuestionType for config_types::FileUploadConfig {
+    type Value = value_types::FileUploadValue;
+
+    const NAME: &'static str = "File upload";
+
+    fn edit(&mut self, ui: &mut egui::Ui, _locale: &Locale) {
+        ui.horizontal(|ui| {
+            ui.label("Allowed file types");
+            // Kept as typed while the field has focus, so that separators
+            // aren't dropped before the next extension is entered.
+            let id = ui.next_auto_id();
+            let mut text = ui
+                .data_mut(|data| data.get_temp::<String>(id))
+                .unwrap_or_else(|| self.allowed_extensions.join(", "));
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut text)
+                    .hint_text("Any, or e.g. pdf, png")
+                    .desired_width(150.0),
+            );
+            if response.changed() {
+                self.allowed_extensions = text
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .map(|extension| extension.trim_start_matches('.').to_lowercase())
+                    .filter(|extension| !extension.is_empty())
+                    .collect();
+            }
+            if response.has_focus() {
+                ui.data_mut(|data| data.insert_temp(id, text));
+            } else {
+                ui.data_mut(|data| data.remove::<String>(id));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Maximum file size");
+            let mut megabytes = self.max_file_size as f64 / MEGABYTE;
+            let response = ui.add(
+                egui::DragValue::new(&mut megabytes)
+                    .clamp_range(0.1..=1024.0)
+                    .speed(0.1)
+                    .suffix(" MB"),
+            );
+            if response.changed() {
+                self.max_file_size = (megabytes * MEGABYTE) as u64;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Maximum number of files");
+            ui.add(egui::DragValue::new(&mut self.max_files).clamp_range(1..=10));
+        });
+    }
+
+    fn default_value(&self) -> Self::Value {
+        Self::Value::default()
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, value: &mut Self::Value, _locale: &Locale) {
+        let mut remove_file = None;
+        for (i, file) in value.files.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "📄 {} ({})",
+                    file.name,
+                    value_types::size_text(file.size)
+                ));
+                if ui.small_button("❌").clicked() {
+                    remove_file = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove_file {
+            value.files.remove(i);
+        }
+        if value.files.len() >= self.max_files {
+            return;
+        }
+
+        // The path being entered and the error from the last attempt.
+        let id = ui.next_auto_id();
+        let (mut path, mut error) = ui
+            .data_mut(|data| data.get_temp::<(String, String)>(id))
+            .unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut path).hint_text("File to upload"));
+            if ui.button("Add file").clicked() {
+                match self.open_file(std::path::Path::new(path.trim())) {
+                    Ok(file) => {
+                        value.files.push(file);
+                        path.clear();
+                        error.clear();
+                    }
+                    Err(message) => error = message,
+                }
+            }
+        });
+        if !error.is_empty() {
+            ui.colored_label(ui.visuals().error_fg_color, &error);
+        }
+        ui.data_mut(|data| data.insert_temp(id, (path, error)));
+    }
+
+    fn validate(&self, value: &Self::Value, _locale: &Locale) -> Result<(), String> {
+        if value.files.len() > self.max_files {
+            return Err(format!("Upload at most {} files", self.max_files));
+        }
+        for file in value.files.iter() {
+            self.check_file(&file.name, file.size)?;
+        }
+        Ok(())
+    }
+
+    fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+        value
+            .files
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+const MEGABYTE: f64 = 1024.0 * 1024.0;
+
+impl config_types::FileUploadConfig {
+    /// Reads the file at `path` into an attachment, if this question accepts it.
+    fn open_file(&self, path: &std::path::Path) -> Result<value_types::Attachment, String> {
+        let name = path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+        let size = std::fs::metadata(path)
+            .map_err(|error| format!("Could not open {}: {}", path.display(), error))?
+            .len();
+        // Checked before reading so that an oversized file is never loaded.
+        self.check_file(&name, size)?;
+        let data = std::fs::read(path)
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+        Ok(value_types::Attachment::new(name, data))
+    }
+}
+
+/// Fields for a time of day, on a 24-hour clock or with an AM/PM menu.
+fn time_input(
+    ui: &mut egui::Ui,
+    value: &mut value_types::TimeValue,
+    twenty_four_hour: bool,
+    seconds: bool,
+) {
+    ui.horizontal(|ui| {
+        if twenty_four_hour {
+            ui.add(egui::DragValue::new(&mut value.hour).clamp_range(0..=23));
+        } else {
+            let (mut hour, period) = value.twelve_hour();
+            if ui
+                .add(egui::DragValue::new(&mut hour).clamp_range(1..=12))
+                .changed()
+            {
+                value.set_twelve_hour(hour, period);
+            }
+        }
+        ui.label(":");
+        ui.add(egui::DragValue::new(&mut value.minute).clamp_range(0..=59));
+        if seconds {
+            ui.label(":");
+            ui.add(egui::DragValue::new(&mut value.second).clamp_range(0..=59));
+        }
+        if !twenty_four_hour {
+            let (hour, mut period) = value.twelve_hour();
+            let label = match period {
+                value_types::DayPeriod::AM => "AM",
+                value_types::DayPeriod::PM => "PM",
+            };
+            ui.menu_button(label, |ui| {
+                for (option, label) in [
+                    (value_types::DayPeriod::AM, "AM"),
+                    (value_types::DayPeriod::PM, "PM"),
+                ] {
+                    if ui.selectable_value(&mut period, option, label).clicked() {
+                        value.set_twelve_hour(hour, period.clone());
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+    });
+}
+
+fn edit_grid(ui: &mut egui::Ui, rows: &mut Vec<String>, columns: &mut Vec<String>) {
+    ui.horizontal(|ui| {
+        ui.vertical(|ui| edit_options(ui, rows, None, "Row"));
+        ui.vertical(|ui| edit_options(ui, columns, None, "Column"));
+    });
+}
+
+/// Width of the pictures shown with options.
+const OPTION_IMAGE_WIDTH: f32 = 160.0;
+
+fn show_option_image(ui: &mut egui::Ui, images: &[Option<Image>], i: usize) {
+    if let Some(Some(image)) = images.get(i) {
+        ui.indent("option image", |ui| image.show(ui, OPTION_IMAGE_WIDTH));
+    }
+}
+
+/// Edits a list of options, rows or columns, along with a picture for each
+/// option if `images` is given.
+fn edit_options(
+    ui: &mut egui::Ui,
+    options: &mut Vec<String>,
+    mut images: Option<&mut Vec<Option<Image>>>,
+    label: impl Into<String>,
+) {
+    let label: String = label.into();
+    ui.label(format!("{}s", label));
+    if let Some(images) = images.as_deref_mut() {
+        images.resize(options.len(), None);
+    }
+    let mut delete_option = None;
+    let mut swap_options = None;
+    let count = options.len();
+    for (i, option) in options.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(option).desired_width(100.0));
+            if ui.add_enabled(i > 0, egui::Button::new("⏶")).clicked() {
+                swap_options = Some((i - 1, i));
+            }
+            if ui
+                .add_enabled(i + 1 < count, egui::Button::new("⏷"))
+                .clicked()
+            {
+                swap_options = Some((i, i + 1));
+            }
+            if ui.button("❌").clicked() {
+                delete_option = Some(i);
+            }
+        });
+        if let Some(images) = images.as_deref_mut() {
+            ui.indent("option image", |ui| {
+                crate::image::edit(ui, &mut images[i], OPTION_IMAGE_WIDTH)
+            });
+        }
+    }
+    if let Some((a, b)) = swap_options {
+        options.swap(a, b);
+        if let Some(images) = images.as_deref_mut() {
+            images.swap(a, b);
+        }
+    }
+    if let Some(i) = delete_option {
+        options.remove(i);
+        if let Some(images) = images.as_deref_mut() {
+            images.remove(i);
+        }
+    }
+    if ui.button(format!("Add {}", label.to_lowercase())).clicked() {
+        options.push(format!("{} {}", label, options.len() + 1));
+        if let Some(images) = images {
+            images.push(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_within_limits() {
+        let date = |year, month, day| value_types::DateValue {
+            year,
+            month,
+            day,
+            time: value_types::TimeValue::default(),
+        };
+        let config = config_types::DateConfig {
+            min: Some(date(2024, 3, 1)),
+            max: Some(date(2024, 3, 31)),
+            ..Default::default()
+        };
+        let locale = Locale::default();
+        assert_eq!(config.validate(&date(0, 0, 0), &locale), Ok(()));
+        assert_eq!(config.validate(&date(2024, 3, 15), &locale), Ok(()));
+        assert_eq!(
+            config.validate(&date(2024, 2, 29), &locale),
+            Err(format!(
+                "Choose a date on or after {}",
+                locale.format_date(&date(2024, 3, 1), true)
+            ))
+        );
+        assert!(config.validate(&date(2024, 4, 1), &locale).is_err());
+        assert!(config.validate(&date(2024, 3, 32), &locale).is_err());
+        // Saved answers with a month that doesn't exist are reported, not a panic.
+        assert!(config.validate(&date(2024, 14, 1), &locale).is_err());
+    }
+
+    #[test]
+    fn answers_follow_the_locale() {
+        let (_, europe) = Locale::presets()[1].clone();
+        let time = value_types::TimeValue {
+            hour: 14,
+            minute: 30,
+            second: 0,
+        };
+        let date = value_types::DateValue {
+            year: 2024,
+            month: 3,
+            day: 1,
+            time: time.clone(),
+        };
+        let config = config_types::DateConfig::default();
+        assert_eq!(config.answer_text(&date, &Locale::default()), "03/01/2024");
+        assert_eq!(config.answer_text(&date, &europe), "01/03/2024");
+        assert_eq!(
+            config.answer_text(&value_types::DateValue::default(), &europe),
+            ""
+        );
+        let config = config_types::TimeConfig::default();
+        let time = Some(time);
+        assert_eq!(config.answer_text(&time, &Locale::default()), "2:30 PM");
+        assert_eq!(config.answer_text(&time, &europe), "14:30");
+        assert_eq!(config.answer_text(&None, &europe), "");
+    }
+
+    #[test]
+    fn scale_answers_must_be_points() {
+        let locale = Locale::default();
+        let config = config_types::LinearScaleConfig {
+            start: 1,
+            end: 6,
+            step: 2,
+            ..Default::default()
+        };
+        let answer = |value| value_types::LinearScaleValue { value };
+        assert_eq!(config.validate(&config.default_value(), &locale), Ok(()));
+        assert_eq!(config.validate(&answer(5), &locale), Ok(()));
+        assert_eq!(
+            config.validate(&answer(2), &locale),
+            Err("2 is not on the scale".into())
+        );
+        assert!(config.validate(&answer(6), &locale).is_err());
+    }
+
+    fn number(value: f64) -> value_types::NumberValue {
+        value_types::NumberValue { value: Some(value) }
+    }
+
+    #[test]
+    fn number_limits_and_steps() {
+        let locale = Locale::default();
+        let config = config_types::NumberConfig {
+            min: Some(1.0),
+            max: Some(3.0),
+            step: 0.25,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(&value_types::NumberValue::default(), &locale),
+            Ok(())
+        );
+        assert_eq!(config.validate(&number(1.75), &locale), Ok(()));
+        assert_eq!(config.validate(&number(3.0), &locale), Ok(()));
+        assert!(config.validate(&number(0.75), &locale).is_err());
+        assert!(config.validate(&number(3.25), &locale).is_err());
+        assert_eq!(
+            config.validate(&number(1.37), &locale),
+            Err("Enter a number in steps of 0.25".into())
+        );
+        assert!(config.validate(&number(f64::NAN), &locale).is_err());
+
+        let config = config_types::NumberConfig {
+            integer: true,
+            ..Default::default()
+        };
+        assert_eq!(config.validate(&number(-4.0), &locale), Ok(()));
+        assert_eq!(
+            config.validate(&number(2.5), &locale),
+            Err("Enter a whole number".into())
+        );
+    }
+
+    #[test]
+    fn number_snaps_to_steps() {
+        let locale = Locale::default();
+        let config = config_types::NumberConfig {
+            min: Some(1.0),
+            max: Some(2.9),
+            step: 0.25,
+            ..Default::default()
+        };
+        assert_eq!(config.snap(1.37), 1.25);
+        assert_eq!(config.snap(1.4), 1.5);
+        // The nearest step past the maximum falls back one step.
+        assert_eq!(config.snap(2.9), 2.75);
+        for dragged in [1.0, 1.13, 1.37, 2.01, 2.6] {
+            assert_eq!(
+                config.validate(&number(config.snap(dragged)), &locale),
+                Ok(())
+            );
+        }
+
+        let config = config_types::NumberConfig {
+            step: 0.1,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(&number(config.snap(0.2999)), &locale),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn empty_scales_can_be_shown() {
+        let locale = Locale::default();
+        for style in config_types::ScaleStyle::ALL {
+            let config = config_types::LinearScaleConfig {
+                start: 5,
+                end: 1,
+                style,
+                ..Default::default()
+            };
+            let mut value = config.default_value();
+            let _ = egui::Context::default().run(Default::default(), |ctx| {
+                egui::CentralPanel::default()
+                    .show(ctx, |ui| config.preview(ui, &mut value, &locale));
+            });
+            assert!(config.validate(&value, &locale).is_err());
+        }
+    }
+}
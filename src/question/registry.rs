@@ -0,0 +1,160 @@
+use std::marker::PhantomData;
+use std::sync::RwLock;
+
+use eframe::egui;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::question_type::CustomQuestionType;
+use crate::locale::Locale;
+
+static REGISTRY: RwLock<Vec<&'static dyn RegisteredType>> = RwLock::new(Vec::new());
+
+/// Adds a custom question type to the type menu, replacing any type registered
+/// earlier under the same tag. Call it before starting the app.
+pub fn register_question_type<T: CustomQuestionType>() {
+    let mut registry = REGISTRY.write().unwrap();
+    registry.retain(|registered| registered.tag() != T::TAG);
+    registry.push(Box::leak(Box::new(Registered::<T>(PhantomData))));
+}
+
+pub fn registered_types() -> Vec<&'static dyn RegisteredType> {
+    REGISTRY.read().unwrap().clone()
+}
+
+pub fn find(tag: &str) -> Option<&'static dyn RegisteredType> {
+    REGISTRY
+        .read()
+        .unwrap()
+        .iter()
+        .find(|registered| registered.tag() == tag)
+        .copied()
+}
+
+/// A custom question's config or answer, saved as JSON under its type's tag.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomData {
+    pub tag: String,
+    pub data: Value,
+}
+
+/// A custom question's config together with its current answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomQuestion {
+    pub tag: String,
+    pub config: Value,
+    pub value: Value,
+}
+
+/// A registered custom question type, working on configs and values as JSON so
+/// that they can be stored without knowing their Rust types.
+pub trait RegisteredType: Send + Sync {
+    fn tag(&self) -> &'static str;
+    fn name(&self) -> &'static str;
+    fn default_config(&self) -> Value;
+    fn default_value(&self, config: &Value) -> Value;
+    fn edit(&self, ui: &mut egui::Ui, config: &mut Value, locale: &Locale);
+    fn preview(&self, ui: &mut egui::Ui, config: &Value, value: &mut Value, locale: &Locale);
+    fn validate(&self, config: &Value, value: &Value, locale: &Locale) -> Result<(), String>;
+    fn answer_text(&self, config: &Value, value: &Value, locale: &Locale) -> String;
+}
+
+struct Registered<T>(PhantomData<fn() -> T>);
+
+impl<T: CustomQuestionType> RegisteredType for Registered<T> {
+    fn tag(&self) -> &'static str {
+        T::TAG
+    }
+
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn default_config(&self) -> Value {
+        to_json(&T::default())
+    }
+
+    fn default_value(&self, config: &Value) -> Value {
+        to_json(&from_json::<T>(config).default_value())
+    }
+
+    fn edit(&self, ui: &mut egui::Ui, config: &mut Value, locale: &Locale) {
+        let mut typed = from_json::<T>(config);
+        let before = typed.clone();
+        typed.edit(ui, locale);
+        if typed != before {
+            *config = to_json(&typed);
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui, config: &Value, value: &mut Value, locale: &Locale) {
+        let mut typed = from_json::<T::Value>(value);
+        let before = typed.clone();
+        from_json::<T>(config).preview(ui, &mut typed, locale);
+        if typed != before {
+            *value = to_json(&typed);
+        }
+    }
+
+    fn validate(&self, config: &Value, value: &Value, locale: &Locale) -> Result<(), String> {
+        from_json::<T>(config).validate(&from_json(value), locale)
+    }
+
+    fn answer_text(&self, config: &Value, value: &Value, locale: &Locale) -> String {
+        from_json::<T>(config).answer_text(&from_json(value), locale)
+    }
+}
+
+/// Reads `value` as `T`, falling back to the default if it no longer matches,
+/// for example after the type's fields have changed.
+fn from_json<T: DeserializeOwned + Default>(value: &Value) -> T {
+    serde_json::from_value(value.clone()).unwrap_or_default()
+}
+
+fn to_json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::question::QuestionType;
+
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Stars {
+        max: u8,
+    }
+
+    impl QuestionType for Stars {
+        type Value = u8;
+
+        const NAME: &'static str = "Stars";
+
+        fn default_value(&self) -> Self::Value {
+            0
+        }
+
+        fn preview(&self, _ui: &mut egui::Ui, _value: &mut Self::Value, _locale: &Locale) {}
+
+        fn answer_text(&self, value: &Self::Value, _locale: &Locale) -> String {
+            format!("{} of {} stars", value, self.max)
+        }
+    }
+
+    impl CustomQuestionType for Stars {
+        const TAG: &'static str = "test-stars";
+    }
+
+    #[test]
+    fn answers_of_custom_types_use_their_text() {
+        register_question_type::<Stars>();
+        let stars = find("test-stars").unwrap();
+        assert_eq!(stars.name(), "Stars");
+        let config = serde_json::json!({ "max": 5 });
+        assert_eq!(stars.default_value(&config), serde_json::json!(0));
+        assert_eq!(
+            stars.answer_text(&config, &serde_json::json!(4), &Locale::default()),
+            "4 of 5 stars"
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use eframe::egui;
+
+use super::{config_types, registry, value_types, Question, QuestionKind, QuestionValue};
+use crate::locale::Locale;
+use crate::response::Response;
+
+/// Aggregated answers to a single question across all responses.
+pub struct QuestionSummary {
+    pub name: String,
+    pub answered: usize,
+    pub summary: Summary,
+}
+
+pub enum Summary {
+    Text(Vec<String>),
+    Counts(Vec<(String, usize)>),
+    Files(Vec<value_types::Attachment>),
+    /// Answers to a number question.
+    Numbers {
+        sum: f64,
+        min: f64,
+        max: f64,
+        /// Formats numbers with the question's unit and decimal places.
+        config: config_types::NumberConfig,
+    },
+    /// Answers to a Net Promoter Score question: scores of 0 to 6, 7 or 8,
+    /// and 9 or 10.
+    NetPromoter {
+        detractors: usize,
+        passives: usize,
+        promoters: usize,
+    },
+    Grid {
+        columns: Vec<String>,
+        rows: Vec<(String, Vec<usize>)>,
+    },
+}
+
+impl Question {
+    /// Sums up the answers in `responses`, writing dates and times in `locale`.
+    pub fn summarize(&self, responses: &[Response], locale: &Locale) -> QuestionSummary {
+        let values: Vec<&QuestionValue> = responses
+            .iter()
+            .filter_map(|response| response.answers.get(&self.id))
+            .collect();
+        let (answered, summary) = match &self.kind {
+            QuestionKind::ShortAnswer(..) | QuestionKind::Paragraph(..) => {
+                let texts: Vec<String> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::ShortAnswer(value) => Some(value.text.clone()),
+                        QuestionValue::Paragraph(value) => Some(value.text.clone()),
+                        _ => None,
+                    })
+                    .filter(|text| !text.is_empty())
+                    .collect();
+                (texts.len(), Summary::Text(texts))
+            }
+            QuestionKind::MultipleChoice(config, _) => {
+                let choices: Vec<&String> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::MultipleChoice(value) => Some(&value.choice),
+                        _ => None,
+                    })
+                    .filter(|choice| !choice.is_empty())
+                    .collect();
+                (choices.len(), count_choices(&config.options, &choices))
+            }
+            QuestionKind::Dropdown(config, _) => {
+                let choices: Vec<&String> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::Dropdown(value) => Some(&value.choice),
+                        _ => None,
+                    })
+                    .filter(|choice| !choice.is_empty())
+                    .collect();
+                (choices.len(), count_choices(&config.options, &choices))
+            }
+            QuestionKind::Checkboxes(config, _) => {
+                let choices: Vec<&Vec<bool>> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::Checkboxes(value) => Some(&value.choices),
+                        _ => None,
+                    })
+                    .filter(|choices| choices.contains(&true))
+                    .collect();
+                let counts = config
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, option)| {
+                        let count = choices
+                            .iter()
+                            .filter(|choices| choices.get(i) == Some(&true))
+                            .count();
+                        (option.clone(), count)
+                    })
+                    .collect();
+                (choices.len(), Summary::Counts(counts))
+            }
+            QuestionKind::LinearScale(config, _) => {
+                let scores: Vec<i64> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::LinearScale(value) => Some(value.value),
+                        _ => None,
+                    })
+                    .collect();
+                if config.style == config_types::ScaleStyle::NetPromoterScore {
+                    let count = |range: std::ops::RangeInclusive<i64>| {
+                        scores.iter().filter(|score| range.contains(score)).count()
+                    };
+                    let summary = Summary::NetPromoter {
+                        detractors: count(0..=6),
+                        passives: count(7..=8),
+                        promoters: count(9..=10),
+                    };
+                    (scores.len(), summary)
+                } else {
+                    let counts = config
+                        .points()
+                        .map(|i| {
+                            let count = scores.iter().filter(|score| **score == i).count();
+                            (i.to_string(), count)
+                        })
+                        .collect();
+                    (scores.len(), Summary::Counts(counts))
+                }
+            }
+            QuestionKind::MultipleChoiceGrid(config, _) => {
+                let choices: Vec<&Vec<String>> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::MultipleChoiceGrid(value) => Some(&value.choices),
+                        _ => None,
+                    })
+                    .filter(|choices| choices.iter().any(|choice| !choice.is_empty()))
+                    .collect();
+                let rows = config
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .map(|(y, row)| {
+                        let counts = config
+                            .columns
+                            .iter()
+                            .map(|column| {
+                                choices
+                                    .iter()
+                                    .filter(|choices| choices.get(y) == Some(column))
+                                    .count()
+                            })
+                            .collect();
+                        (row.clone(), counts)
+                    })
+                    .collect();
+                let columns = config.columns.clone();
+                (choices.len(), Summary::Grid { columns, rows })
+            }
+            QuestionKind::CheckboxGrid(config, _) => {
+                let choices: Vec<&Vec<Vec<bool>>> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::CheckboxGrid(value) => Some(&value.choices),
+                        _ => None,
+                    })
+                    .filter(|choices| choices.iter().any(|row| row.contains(&true)))
+                    .collect();
+                let rows = config
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .map(|(y, row)| {
+                        let counts = (0..config.columns.len())
+                            .map(|x| {
+                                choices
+                                    .iter()
+                                    .filter(|choices| {
+                                        choices.get(y).and_then(|row| row.get(x)) == Some(&true)
+                                    })
+                                    .count()
+                            })
+                            .collect();
+                        (row.clone(), counts)
+                    })
+                    .collect();
+                let columns = config.columns.clone();
+                (choices.len(), Summary::Grid { columns, rows })
+            }
+            QuestionKind::Date(config, _) => {
+                let dates: Vec<String> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::Date(value) if !value.is_empty() => {
+                            Some(config.format(value, locale))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                (dates.len(), Summary::Text(dates))
+            }
+            QuestionKind::Time(config, _) => {
+                let times: Vec<String> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::Time(Some(value)) => Some(config.format(value, locale)),
+                        _ => None,
+                    })
+                    .collect();
+                (times.len(), Summary::Text(times))
+            }
+            QuestionKind::Number(config, _) => {
+                let numbers: Vec<f64> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::Number(value) => value.value,
+                        _ => None,
+                    })
+                    .collect();
+                let summary = Summary::Numbers {
+                    sum: numbers.iter().sum(),
+                    min: numbers.iter().copied().fold(f64::INFINITY, f64::min),
+                    max: numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                    config: config.clone(),
+                };
+                (numbers.len(), summary)
+            }
+            QuestionKind::FileUpload(..) => {
+                let uploads: Vec<&Vec<value_types::Attachment>> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::FileUpload(value) => Some(&value.files),
+                        _ => None,
+                    })
+                    .filter(|files| !files.is_empty())
+                    .collect();
+                let files = uploads.iter().flat_map(|files| files.iter()).cloned();
+                (uploads.len(), Summary::Files(files.collect()))
+            }
+            QuestionKind::Email(..)
+            | QuestionKind::Phone(..)
+            | QuestionKind::Url(..)
+            | QuestionKind::Address(..) => {
+                let answers: Vec<String> = values
+                    .iter()
+                    .filter_map(|value| self.kind.answer_text(value, locale))
+                    .filter(|answer| !answer.is_empty())
+                    .collect();
+                (answers.len(), Summary::Text(answers))
+            }
+            QuestionKind::Custom(custom) => {
+                let registered = registry::find(&custom.tag);
+                let answers: Vec<String> = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        QuestionValue::Custom(value) if value.tag == custom.tag => {
+                            Some(match registered {
+                                Some(t) => t.answer_text(&custom.config, &value.data, locale),
+                                None => value.data.to_string(),
+                            })
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                (answers.len(), Summary::Text(answers))
+            }
+        };
+        QuestionSummary {
+            name: self.name.clone(),
+            answered,
+            summary,
+        }
+    }
+}
+
+fn count_choices(options: &[String], choices: &[&String]) -> Summary {
+    Summary::Counts(
+        options
+            .iter()
+            .map(|option| {
+                let count = choices.iter().filter(|choice| **choice == option).count();
+                (option.clone(), count)
+            })
+            .collect(),
+    )
+}
+
+impl QuestionSummary {
+    pub fn show(&self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.label(egui::RichText::new(&self.name).strong());
+            ui.label(format!("{} responses", self.answered));
+            match &self.summary {
+                Summary::Text(texts) => {
+                    for text in texts.iter() {
+                        ui.label(text);
+                    }
+                }
+                Summary::Files(files) => {
+                    for file in files.iter() {
+                        ui.label(format!(
+                            "📄 {} ({})",
+                            file.name,
+                            value_types::size_text(file.size)
+                        ))
+                        .on_hover_text(format!("SHA-256 {}", file.hash));
+                    }
+                }
+                Summary::Numbers { .. } => {
+                    egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                        for (label, number) in self.number_stats() {
+                            ui.label(label);
+                            ui.label(number);
+                            ui.end_row();
+                        }
+                    });
+                }
+                Summary::NetPromoter { .. } => {
+                    ui.label(format!(
+                        "Net Promoter Score: {:+.0}",
+                        self.net_promoter_score()
+                    ));
+                    egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                        for (label, count) in self.promoter_counts() {
+                            ui.label(label);
+                            ui.add(
+                                egui::ProgressBar::new(self.fraction(count))
+                                    .desired_width(200.0)
+                                    .text(self.caption(count)),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                }
+                Summary::Counts(counts) => {
+                    egui::Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                        for (label, count) in counts.iter() {
+                            ui.label(label);
+                            ui.add(
+                                egui::ProgressBar::new(self.fraction(*count))
+                                    .desired_width(200.0)
+                                    .text(self.caption(*count)),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                }
+                Summary::Grid { columns, rows } => {
+                    egui::Grid::new(ui.next_auto_id())
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("");
+                            for column in columns.iter() {
+                                ui.label(column);
+                            }
+                            ui.end_row();
+                            for (row, counts) in rows.iter() {
+                                ui.label(row);
+                                for count in counts.iter() {
+                                    ui.label(count.to_string());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+            }
+        });
+    }
+
+    /// The share of answering respondents that `count` represents.
+    pub fn fraction(&self, count: usize) -> f32 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            count as f32 / self.answered as f32
+        }
+    }
+
+    /// The sum, mean, minimum and maximum with their labels, if this is a
+    /// summary of numbers with any answers.
+    pub fn number_stats(&self) -> Vec<(&'static str, String)> {
+        match &self.summary {
+            Summary::Numbers {
+                sum,
+                min,
+                max,
+                config,
+            } if self.answered > 0 => vec![
+                ("Sum", config.format(*sum)),
+                ("Mean", config.format(*sum / self.answered as f64)),
+                ("Minimum", config.format(*min)),
+                ("Maximum", config.format(*max)),
+            ],
+            _ => vec![],
+        }
+    }
+
+    /// The percentage of promoters minus the percentage of detractors, from
+    /// -100 to 100, or 0 if this isn't a Net Promoter Score summary.
+    pub fn net_promoter_score(&self) -> f32 {
+        match self.summary {
+            Summary::NetPromoter {
+                detractors,
+                promoters,
+                ..
+            } => (self.fraction(promoters) - self.fraction(detractors)) * 100.0,
+            _ => 0.0,
+        }
+    }
+
+    /// The detractor, passive and promoter counts with their labels, if this
+    /// is a Net Promoter Score summary.
+    pub fn promoter_counts(&self) -> Vec<(&'static str, usize)> {
+        match self.summary {
+            Summary::NetPromoter {
+                detractors,
+                passives,
+                promoters,
+            } => vec![
+                ("Detractors (0-6)", detractors),
+                ("Passives (7-8)", passives),
+                ("Promoters (9-10)", promoters),
+            ],
+            _ => vec![],
+        }
+    }
+
+    pub fn caption(&self, count: usize) -> String {
+        format!("{} ({:.0}%)", count, self.fraction(count) * 100.0)
+    }
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use super::registry::CustomData;
 use super::value_types;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum QuestionValue {
     ShortAnswer(value_types::ShortAnswerValue),
     Paragraph(value_types::ParagraphValue),
@@ -13,40 +14,12 @@ pub enum QuestionValue {
     MultipleChoiceGrid(value_types::MultipleChoiceGridValue),
     CheckboxGrid(value_types::CheckboxGridValue),
     Date(value_types::DateValue),
-    Time(value_types::TimeValue),
-}
-
-impl QuestionValue {
-    pub fn types_list() -> Vec<Self> {
-        vec![
-            Self::ShortAnswer(value_types::ShortAnswerValue::default()),
-            Self::Paragraph(value_types::ParagraphValue::default()),
-            Self::MultipleChoice(value_types::MultipleChoiceValue::default()),
-            Self::Checkboxes(value_types::CheckboxesValue::default()),
-            Self::Dropdown(value_types::DropdownValue::default()),
-            Self::LinearScale(value_types::LinearScaleValue::default()),
-            Self::MultipleChoiceGrid(value_types::MultipleChoiceGridValue::default()),
-            Self::CheckboxGrid(value_types::CheckboxGridValue::default()),
-            Self::Date(value_types::DateValue::default()),
-            Self::Time(value_types::TimeValue::default()),
-        ]
-    }
-}
-
-impl PartialEq for QuestionValue {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::ShortAnswer(_), Self::ShortAnswer(_)) => true,
-            (Self::Paragraph(_), Self::Paragraph(_)) => true,
-            (Self::MultipleChoice(_), Self::MultipleChoice(_)) => true,
-            (Self::Checkboxes(_), Self::Checkboxes(_)) => true,
-            (Self::Dropdown(_), Self::Dropdown(_)) => true,
-            (Self::LinearScale(_), Self::LinearScale(_)) => true,
-            (Self::MultipleChoiceGrid(_), Self::MultipleChoiceGrid(_)) => true,
-            (Self::CheckboxGrid(_), Self::CheckboxGrid(_)) => true,
-            (Self::Date(_), Self::Date(_)) => true,
-            (Self::Time(_), Self::Time(_)) => true,
-            _ => false,
-        }
-    }
+    Time(#[serde(deserialize_with = "value_types::saved_time")] Option<value_types::TimeValue>),
+    FileUpload(value_types::FileUploadValue),
+    Number(value_types::NumberValue),
+    Email(value_types::EmailValue),
+    Phone(value_types::PhoneValue),
+    Url(value_types::UrlValue),
+    Address(value_types::AddressValue),
+    Custom(CustomData),
 }
@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use crate::form::Form;
+use crate::pdf::{Font, Pdf};
+use crate::question::{value_types, QuestionKind, QuestionValue, Summary};
+
+/// Renders the response summary of `form` as a PDF document.
+pub fn pdf_report(form: &Form) -> Vec<u8> {
+    let mut pdf = Pdf::new();
+    pdf.text(&form.name, Font::Bold, 20.0, 0.0);
+    pdf.text(
+        &format!("{} responses", form.responses.len()),
+        Font::Regular,
+        11.0,
+        0.0,
+    );
+    for question in form.questions() {
+        let summary = question.summarize(&form.responses, &form.locale);
+        pdf.space(14.0);
+        pdf.reserve(48.0);
+        pdf.text(&summary.name, Font::Bold, 13.0, 0.0);
+        pdf.text(
+            &format!("{} responses", summary.answered),
+            Font::Regular,
+            10.0,
+            0.0,
+        );
+        pdf.space(4.0);
+        match &summary.summary {
+            Summary::Text(texts) => {
+                for text in texts.iter() {
+                    pdf.text(&format!("• {}", text), Font::Regular, 10.0, 8.0);
+                }
+            }
+            Summary::Files(files) => {
+                for file in files.iter() {
+                    let text = format!("• {} ({})", file.name, value_types::size_text(file.size));
+                    pdf.text(&text, Font::Regular, 10.0, 8.0);
+                }
+            }
+            Summary::Numbers { .. } => {
+                for (label, number) in summary.number_stats() {
+                    let text = format!("{}: {}", label, number);
+                    pdf.text(&text, Font::Regular, 10.0, 8.0);
+                }
+            }
+            Summary::NetPromoter { .. } => {
+                let score = format!("Net Promoter Score: {:+.0}", summary.net_promoter_score());
+                pdf.text(&score, Font::Bold, 10.0, 8.0);
+                for (label, count) in summary.promoter_counts() {
+                    pdf.bar(label, summary.fraction(count), &summary.caption(count), 8.0);
+                }
+            }
+            Summary::Counts(counts) => {
+                for (label, count) in counts.iter() {
+                    pdf.bar(
+                        label,
+                        summary.fraction(*count),
+                        &summary.caption(*count),
+                        8.0,
+                    );
+                }
+            }
+            Summary::Grid { columns, rows } => {
+                for (row, counts) in rows.iter() {
+                    pdf.text(row, Font::Bold, 10.0, 8.0);
+                    for (column, count) in columns.iter().zip(counts) {
+                        pdf.bar(
+                            column,
+                            summary.fraction(*count),
+                            &summary.caption(*count),
+                            16.0,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    pdf.finish()
+}
+
+/// The responses to `form` as CSV, one row per response and one column per
+/// question, except addresses, which get a column for each part.
+pub fn csv_export(form: &Form) -> String {
+    let mut header = vec!["Response".to_owned()];
+    for question in form.questions() {
+        if let QuestionKind::Address(..) = question.kind {
+            for (part, _) in value_types::AddressValue::default().parts() {
+                header.push(format!("{} ({})", question.name, part));
+            }
+        } else {
+            header.push(question.name.clone());
+        }
+    }
+    let mut csv = csv_row(&header);
+    for (i, response) in form.responses.iter().enumerate() {
+        let mut row = vec![(i + 1).to_string()];
+        for question in form.questions() {
+            let value = response.answers.get(&question.id);
+            if let QuestionKind::Address(..) = question.kind {
+                let address = match value {
+                    Some(QuestionValue::Address(address)) => address.clone(),
+                    _ => Default::default(),
+                };
+                row.extend(address.parts().iter().map(|(_, part)| part.to_string()));
+            } else {
+                let text = value.and_then(|value| question.kind.answer_text(value, &form.locale));
+                row.push(text.unwrap_or_default());
+            }
+        }
+        csv.push_str(&csv_row(&row));
+    }
+    csv
+}
+
+/// A line of CSV, quoting fields that need it.
+fn csv_row(fields: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    format!("{}\r\n", fields.join(","))
+}
+
+/// Writes every file uploaded in the responses to `form` into the folder at
+/// `dir`, with a subfolder per response, returning how many were written.
+pub fn save_attachments(form: &Form, dir: &Path) -> io::Result<usize> {
+    let mut saved = 0;
+    for (i, response) in form.responses.iter().enumerate() {
+        let response_dir = dir.join(format!("Response {}", i + 1));
+        let mut names = HashSet::new();
+        for question in form.questions() {
+            let Some(QuestionValue::FileUpload(value)) = response.answers.get(&question.id) else {
+                continue;
+            };
+            for file in value.files.iter() {
+                // Only the last part of the name, so that a name can't point
+                // outside the folder.
+                let name = Path::new(&file.name)
+                    .file_name()
+                    .map_or_else(|| "file".into(), |name| name.to_string_lossy().into_owned());
+                let mut unique = name.clone();
+                let mut n = 1;
+                while !names.insert(unique.clone()) {
+                    n += 1;
+                    unique = format!("{} {}", n, name);
+                }
+                std::fs::create_dir_all(&response_dir)?;
+                std::fs::write(response_dir.join(unique), &file.data)?;
+                saved += 1;
+            }
+        }
+    }
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quoting() {
+        let fields = ["plain", "a, b", "say \"hi\"", "two\nlines", ""].map(String::from);
+        assert_eq!(
+            csv_row(&fields),
+            "plain,\"a, b\",\"say \"\"hi\"\"\",\"two\nlines\",\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_columns() {
+        let mut form = Form::builder("Contacts")
+            .short_answer("Name, full")
+            .address("Home")
+            .build();
+        for question in form.questions_mut() {
+            match &mut question.kind {
+                QuestionKind::ShortAnswer(_, value) => value.text = "Ada".into(),
+                QuestionKind::Address(_, value) => {
+                    value.street = "1 Main St".into();
+                    value.city = "Springfield".into();
+                    value.country = "USA".into();
+                }
+                _ => {}
+            }
+        }
+        assert!(form.submit());
+        let csv = csv_export(&form);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "Response,\"Name, full\",Home (Street),Home (City),Home (Region),\
+             Home (Postal code),Home (Country)"
+        );
+        assert_eq!(lines[1], "1,Ada,1 Main St,Springfield,,,USA");
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn unanswered_times_are_left_out() {
+        let mut form = Form::builder("Shifts").time("Start").build();
+        assert!(form.submit());
+        for question in form.questions_mut() {
+            if let QuestionKind::Time(_, value) = &mut question.kind {
+                *value = Some(value_types::TimeValue {
+                    hour: 9,
+                    minute: 0,
+                    second: 0,
+                });
+            }
+        }
+        assert!(form.submit());
+        let csv = csv_export(&form);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[1..3], ["1,", "2,9:00 AM"]);
+        let question = form.questions().next().unwrap();
+        let summary = question.summarize(&form.responses, &form.locale);
+        assert_eq!(summary.answered, 1);
+    }
+}
@@ -0,0 +1,9 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A submitted set of answers, keyed by question id.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Response {
+    pub answers: HashMap<usize, crate::question::QuestionValue>,
+}
@@ -0,0 +1,39 @@
+//! Forms bundled with eform to start new forms from.
+
+use std::sync::OnceLock;
+
+use crate::form::Form;
+
+const BUILT_IN: [&str; 5] = [
+    include_str!("../templates/contact_info.form.json"),
+    include_str!("../templates/event_rsvp.form.json"),
+    include_str!("../templates/feedback_survey.form.json"),
+    include_str!("../templates/quiz.form.json"),
+    include_str!("../templates/job_application.form.json"),
+];
+
+pub fn built_in() -> &'static [Form] {
+    static TEMPLATES: OnceLock<Vec<Form>> = OnceLock::new();
+    TEMPLATES.get_or_init(|| {
+        BUILT_IN
+            .iter()
+            .map(|json| serde_json::from_str(json).expect("bundled template is a valid form"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_templates_load() {
+        for (i, json) in BUILT_IN.iter().enumerate() {
+            let form: Form = serde_json::from_str(json)
+                .unwrap_or_else(|error| panic!("template {} is not a valid form: {}", i, error));
+            assert!(!form.name.is_empty(), "template {} has no name", i);
+            assert!(!form.items.is_empty(), "template {} has no questions", i);
+        }
+        assert_eq!(built_in().len(), BUILT_IN.len());
+    }
+}
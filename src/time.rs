@@ -0,0 +1,96 @@
+//! Wall-clock time, as whole seconds since the Unix epoch.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// `SystemTime` isn't available in the browser, so ask JavaScript instead.
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Today's year, month and day in the user's time zone.
+pub fn today() -> (i64, u32, u32) {
+    civil_from_days((now() as i64 + utc_offset()).div_euclid(86400))
+}
+
+/// Seconds the user's clock is ahead of UTC.
+#[cfg(not(target_arch = "wasm32"))]
+fn utc_offset() -> i64 {
+    chrono::Local::now().offset().local_minus_utc() as i64
+}
+
+/// JavaScript gives the offset in minutes behind UTC.
+#[cfg(target_arch = "wasm32")]
+fn utc_offset() -> i64 {
+    -(js_sys::Date::new_0().get_timezone_offset() * 60.0) as i64
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// The number of days in `month` (1 to 12) of `year`.
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The day of the week of a date, from 0 for Monday to 6 for Sunday.
+pub fn weekday(year: i64, month: u32, day: u32) -> u32 {
+    (days_from_civil(year, month, day) + 3).rem_euclid(7) as u32
+}
+
+/// `secs` as "YYYY-MM-DD HH:MM UTC".
+pub fn format(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let minutes = secs % 86400 / 60;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// The number of days from 1970-01-01 to a date, the inverse of
+/// [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The year, month and day of the `days`th day after 1970-01-01, from Howard
+/// Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,107 @@
+use eframe::egui;
+
+use crate::block::Block;
+use crate::form::{Form, FormItem};
+
+/// The question editor from eform's Questions tab, for use in any [`egui::Ui`].
+/// Edits are recorded in the form's undo history.
+pub struct FormEditor<'a> {
+    form: &'a mut Form,
+}
+
+impl<'a> FormEditor<'a> {
+    pub fn new(form: &'a mut Form) -> Self {
+        Self { form }
+    }
+
+    pub fn show(self, ui: &mut egui::Ui) {
+        let form = self.form;
+        ui.add(
+            egui::TextEdit::multiline(&mut form.description)
+                .hint_text("Form description (optional)")
+                .desired_width(f32::INFINITY)
+                .desired_rows(2),
+        );
+        let mut item_action = None;
+        let count = form.items.len();
+        for (i, item) in form.items.iter_mut().enumerate() {
+            if let Some(action) = item.edit(ui, i == 0, i + 1 == count, &form.locale) {
+                item_action = Some((i, action));
+            }
+        }
+        ui.horizontal(|ui| {
+            if ui.button("Add question").clicked() {
+                let question = form.new_question();
+                form.items.push(FormItem::Question(question));
+            }
+            if ui.button("Add title and description").clicked() {
+                form.items.push(FormItem::Block(Block::Text {
+                    title: "Title".into(),
+                    text: String::new(),
+                }));
+            }
+            if ui.button("Add image").clicked() {
+                form.items.push(FormItem::Block(Block::Image {
+                    image: None,
+                    caption: String::new(),
+                }));
+            }
+            if ui.button("Add divider").clicked() {
+                form.items.push(FormItem::Block(Block::Divider));
+            }
+        });
+        if let Some((i, action)) = item_action {
+            form.apply_action(i, action);
+        }
+        ui.input(|input| form.record_edits(input));
+    }
+}
+
+/// The form as a respondent sees it, as in eform's Preview tab, with buttons to
+/// submit the answers as a response or clear them.
+pub struct FormFiller<'a> {
+    form: &'a mut Form,
+}
+
+impl<'a> FormFiller<'a> {
+    pub fn new(form: &'a mut Form) -> Self {
+        Self { form }
+    }
+
+    /// Returns whether a response was submitted this frame.
+    pub fn show(self, ui: &mut egui::Ui) -> bool {
+        let form = self.form;
+        if !form.description.is_empty() {
+            crate::markdown::show(ui, &form.description);
+            ui.separator();
+        }
+        for i in 0..form.items.len() {
+            form.items[i].preview(ui, &form.locale);
+            let error = form.items[i]
+                .question()
+                .and_then(|question| form.error(question.id));
+            if let Some(error) = error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        }
+        let mut submitted = false;
+        if ui.button("Submit").clicked() {
+            submitted = form.submit();
+        }
+        if ui.button("Clear form").clicked() {
+            form.reset_values();
+        }
+        submitted
+    }
+}
+
+/// Whether `input` has anything that could change a form, which moving the
+/// pointer alone can't.
+pub(crate) fn can_edit(input: &egui::InputState) -> bool {
+    input.events.iter().any(|event| {
+        !matches!(
+            event,
+            egui::Event::PointerMoved(_) | egui::Event::PointerGone
+        )
+    })
+}
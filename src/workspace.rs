@@ -0,0 +1,327 @@
+//! Workspace folders, where each form is saved as its own file with its
+//! responses in a file next to it.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::form::Form;
+
+const FORM_EXTENSION: &str = ".form.json";
+const RESPONSES_EXTENSION: &str = ".responses.json";
+const BACKUPS_DIR: &str = "backups";
+/// Seconds between backups, so that a backup covers a stretch of work rather
+/// than every autosave.
+const BACKUP_INTERVAL: u64 = 10 * 60;
+const MAX_BACKUPS: usize = 20;
+
+/// The folder for forms that aren't in a workspace the user opened, next to
+/// eframe's own app storage. There is none in the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn default_dir() -> Option<PathBuf> {
+    directories_next::ProjectDirs::from("", "", "eform").map(|dirs| dirs.data_dir().join("forms"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn default_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Reads every form in the folder at `dir`, in order of file name, along with
+/// a message for each form that couldn't be read. Those forms are skipped,
+/// and their files left alone, so that one bad file doesn't hide the rest.
+pub fn load(dir: &Path) -> io::Result<(Vec<Form>, Vec<String>)> {
+    let mut stems: Vec<String> = form_files(dir)?
+        .iter()
+        .filter_map(|name| name.strip_suffix(FORM_EXTENSION))
+        .map(|stem| stem.to_owned())
+        .collect();
+    stems.sort();
+
+    let mut forms = vec![];
+    let mut skipped = vec![];
+    for stem in stems {
+        match load_form(dir, &stem) {
+            Ok(form) => forms.push(form),
+            Err(error) => skipped.push(format!("Skipped {}: {}", stem, error)),
+        }
+    }
+    Ok((forms, skipped))
+}
+
+/// Reads the form saved as `stem` in the folder at `dir`, with its responses.
+fn load_form(dir: &Path, stem: &str) -> io::Result<Form> {
+    let mut form: Form = serde_json::from_slice(&fs::read(form_path(dir, stem))?)?;
+    match fs::read(responses_path(dir, stem)) {
+        Ok(responses) => form.responses = serde_json::from_slice(&responses)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) => return Err(error),
+    }
+    form.file_stem = Some(stem.to_owned());
+    form.saved_hash = Some(form.content_hash());
+    Ok(form)
+}
+
+/// Writes the forms that changed since they were last saved to the folder at
+/// `dir`, naming the files of new forms after the form, and backs the folder
+/// up if anything was written and the last backup is old enough.
+pub fn save(dir: &Path, forms: &mut [Form]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut written = false;
+    for i in 0..forms.len() {
+        let hash = forms[i].content_hash();
+        if forms[i].file_stem.is_some() && forms[i].saved_hash == Some(hash) {
+            continue;
+        }
+        if forms[i].file_stem.is_none() {
+            forms[i].file_stem = Some(unique_stem(dir, &forms[i].name, forms));
+        }
+        let form = &forms[i];
+        let stem = form.file_stem.as_deref().unwrap_or_default();
+        let mut data = serde_json::to_value(form)?;
+        if let Some(fields) = data.as_object_mut() {
+            fields.remove("responses");
+        }
+        write_atomic(&form_path(dir, stem), &serde_json::to_vec_pretty(&data)?)?;
+        write_atomic(
+            &responses_path(dir, stem),
+            &serde_json::to_vec_pretty(&form.responses)?,
+        )?;
+        forms[i].saved_hash = Some(hash);
+        written = true;
+    }
+    if !written {
+        return Ok(());
+    }
+    let last_backup = list_backups(dir)?.first().map_or(0, |backup| backup.time);
+    if crate::time::now().saturating_sub(last_backup) >= BACKUP_INTERVAL {
+        back_up(dir)?;
+    }
+    Ok(())
+}
+
+/// Writes `contents` to a temporary file next to `path` and then renames it
+/// over `path`, so that a crash midway leaves either the old or the new file.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    let mut file = fs::File::create(&temp_path)?;
+    io::Write::write_all(&mut file, contents)?;
+    file.sync_all()?;
+    fs::rename(temp_path, path)
+}
+
+/// A copy of a workspace's form files taken while saving.
+pub struct Backup {
+    pub path: PathBuf,
+    /// When the backup was taken, in seconds since the Unix epoch.
+    pub time: u64,
+    pub forms: usize,
+}
+
+/// The backups of the folder at `dir`, newest first.
+pub fn list_backups(dir: &Path) -> io::Result<Vec<Backup>> {
+    let entries = match fs::read_dir(dir.join(BACKUPS_DIR)) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(error),
+    };
+    let mut backups = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let Some(time) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split('-').next()?.parse().ok())
+        else {
+            continue;
+        };
+        let forms = form_files(&path)?
+            .iter()
+            .filter(|name| name.ends_with(FORM_EXTENSION))
+            .count();
+        backups.push(Backup { path, time, forms });
+    }
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.time));
+    Ok(backups)
+}
+
+/// Copies the form files in `dir` into a new backup, dropping the oldest
+/// backups past the limit.
+fn back_up(dir: &Path) -> io::Result<()> {
+    let time = crate::time::now();
+    let mut backup_dir = dir.join(BACKUPS_DIR).join(time.to_string());
+    let mut n = 1;
+    while backup_dir.exists() {
+        n += 1;
+        backup_dir.set_file_name(format!("{}-{}", time, n));
+    }
+    fs::create_dir_all(&backup_dir)?;
+    for name in form_files(dir)? {
+        fs::copy(dir.join(&name), backup_dir.join(&name))?;
+    }
+    for backup in list_backups(dir)?.into_iter().skip(MAX_BACKUPS) {
+        fs::remove_dir_all(backup.path)?;
+    }
+    Ok(())
+}
+
+/// Replaces the forms in `dir` with those in `backup`, backing up the current
+/// forms first so that the restore can itself be undone.
+pub fn restore(dir: &Path, backup: &Backup) -> io::Result<(Vec<Form>, Vec<String>)> {
+    back_up(dir)?;
+    for name in form_files(dir)? {
+        fs::remove_file(dir.join(name))?;
+    }
+    for name in form_files(&backup.path)? {
+        let contents = fs::read(backup.path.join(&name))?;
+        write_atomic(&dir.join(name), &contents)?;
+    }
+    load(dir)
+}
+
+/// Names of the form and response files directly in `dir`.
+fn form_files(dir: &Path) -> io::Result<Vec<String>> {
+    let mut names = vec![];
+    for entry in fs::read_dir(dir)? {
+        let Ok(name) = entry?.file_name().into_string() else {
+            continue;
+        };
+        if name.ends_with(FORM_EXTENSION) || name.ends_with(RESPONSES_EXTENSION) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+/// Deletes the files of `form` from the folder at `dir`.
+pub fn remove(dir: &Path, form: &Form) -> io::Result<()> {
+    let Some(stem) = &form.file_stem else {
+        return Ok(());
+    };
+    for path in [form_path(dir, stem), responses_path(dir, stem)] {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn form_path(dir: &Path, stem: &str) -> PathBuf {
+    dir.join(format!("{}{}", stem, FORM_EXTENSION))
+}
+
+fn responses_path(dir: &Path, stem: &str) -> PathBuf {
+    dir.join(format!("{}{}", stem, RESPONSES_EXTENSION))
+}
+
+/// A file name for a form called `name` that no file in `dir` or other form
+/// is using yet.
+fn unique_stem(dir: &Path, name: &str, forms: &[Form]) -> String {
+    let base: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == ' ' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let base = if base.is_empty() { "form".into() } else { base };
+    let mut stem = base.clone();
+    let mut n = 1;
+    while form_path(dir, &stem).exists()
+        || forms
+            .iter()
+            .any(|form| form.file_stem.as_deref() == Some(&*stem))
+    {
+        n += 1;
+        stem = format!("{} {}", base, n);
+    }
+    stem
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// An empty folder for a test, removed again when dropped.
+    pub(crate) struct TempDir(pub PathBuf);
+
+    impl TempDir {
+        pub(crate) fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("eform-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = TempDir::new("save-and-load");
+        let mut forms = vec![Form::new(), Form::new()];
+        forms[0].name = "Survey".into();
+        forms[1].name = "Survey".into();
+        save(&dir.0, &mut forms).unwrap();
+        assert_eq!(forms[0].file_stem.as_deref(), Some("Survey"));
+        assert_eq!(forms[1].file_stem.as_deref(), Some("Survey 2"));
+
+        let (loaded, skipped) = load(&dir.0).unwrap();
+        assert!(skipped.is_empty());
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].file_stem.as_deref(), Some("Survey"));
+    }
+
+    #[test]
+    fn load_skips_bad_files() {
+        let dir = TempDir::new("load-skips-bad-files");
+        let mut forms = vec![Form::new(), Form::new(), Form::new()];
+        for (form, name) in forms.iter_mut().zip(["a", "b", "c"]) {
+            form.name = name.into();
+        }
+        save(&dir.0, &mut forms).unwrap();
+        fs::write(form_path(&dir.0, "a"), "not json").unwrap();
+        fs::write(responses_path(&dir.0, "b"), "[{").unwrap();
+
+        let (loaded, skipped) = load(&dir.0).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "c");
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped[0].starts_with("Skipped a:"));
+        // A new form doesn't take over the file of a skipped one.
+        assert_eq!(unique_stem(&dir.0, "a", &loaded), "a 2");
+    }
+
+    #[test]
+    fn save_writes_only_changed_forms() {
+        let dir = TempDir::new("save-writes-only-changed-forms");
+        let mut forms = vec![Form::new(), Form::new()];
+        forms[0].name = "a".into();
+        forms[1].name = "b".into();
+        save(&dir.0, &mut forms).unwrap();
+        fs::write(form_path(&dir.0, "a"), "unchanged").unwrap();
+        fs::write(form_path(&dir.0, "b"), "unchanged").unwrap();
+
+        forms[1].description = "Edited".into();
+        save(&dir.0, &mut forms).unwrap();
+        assert_eq!(
+            fs::read_to_string(form_path(&dir.0, "a")).unwrap(),
+            "unchanged"
+        );
+        assert_ne!(
+            fs::read_to_string(form_path(&dir.0, "b")).unwrap(),
+            "unchanged"
+        );
+    }
+}